@@ -0,0 +1,269 @@
+// #[derive(Builder)]
+// Generates a FooBuilder for a struct Foo, written like the hand-written builders of the
+// design_patterns crate: required fields are the arguments of new(), every other field gets a
+// `&mut Self` setter, and build(&mut self) clones the collected values into a Foo.
+//
+//     #[derive(Builder)]
+//     #[builder(validate = FooBuilder::validate, error = FooError)]
+//     pub struct Foo {
+//         name: String,            // required, FooBuilder::new(name)
+//         #[builder(default)]
+//         retries: u32,            // setter, Default::default() when it isn't called
+//         #[builder(default = 30)]
+//         timeout: u64,            // setter, 30 when it isn't called
+//         comment: Option<String>, // setter taking a String, None when it isn't called
+//     }
+//
+// The validation hook is a fn(&FooBuilder) -> Result<(), FooError>. build() calls it first and
+// returns Result<Foo, FooError>. Without a hook build() returns the Foo itself.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+use syn::{
+    parse_macro_input, Attribute, Data, DeriveInput, Error, Expr, Fields, GenericArgument, Ident,
+    Path, PathArguments, Result, Type,
+};
+
+#[proc_macro_derive(Builder, attributes(builder))]
+pub fn derive_builder(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+enum Kind {
+    // Argument of new()
+    Required,
+    // Option<T> field, the setter takes a T
+    Optional(Type),
+    // Setter, with the value used when it isn't called
+    Default(Option<Expr>),
+}
+
+struct Field {
+    ident: Ident,
+    ty: Type,
+    kind: Kind,
+}
+
+impl Field {
+    fn parse(field: &syn::Field) -> Result<Self> {
+        let ident = field.ident.clone().expect("named field");
+        let mut default = None;
+        for attr in builder_attrs(&field.attrs) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("default") {
+                    default = Some(if meta.input.peek(syn::Token![=]) {
+                        Some(meta.value()?.parse()?)
+                    } else {
+                        None
+                    });
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `default` or `default = ...`"))
+                }
+            })?;
+        }
+
+        let kind = match (option_inner(&field.ty), default) {
+            (Some(_), Some(_)) => {
+                let message = "Option fields are optional already, drop `default`";
+                return Err(Error::new(field.span(), message));
+            }
+            (Some(inner), None) => Kind::Optional(inner.clone()),
+            (None, Some(default)) => Kind::Default(default),
+            (None, None) => Kind::Required,
+        };
+
+        Ok(Self {
+            ident,
+            ty: field.ty.clone(),
+            kind,
+        })
+    }
+
+    // Type of the field in the builder
+    fn stored(&self) -> TokenStream2 {
+        let ty = &self.ty;
+        match self.kind {
+            Kind::Required | Kind::Optional(_) => quote!(#ty),
+            Kind::Default(_) => quote!(::core::option::Option<#ty>),
+        }
+    }
+
+    fn setter(&self) -> Option<TokenStream2> {
+        let ident = &self.ident;
+        let ty = match &self.kind {
+            Kind::Required => return None,
+            Kind::Optional(inner) => inner,
+            Kind::Default(_) => &self.ty,
+        };
+        Some(quote! {
+            pub fn #ident(&mut self, #ident: #ty) -> &mut Self {
+                self.#ident = ::core::option::Option::Some(#ident);
+                self
+            }
+        })
+    }
+
+    fn value(&self) -> TokenStream2 {
+        let ident = &self.ident;
+        match &self.kind {
+            Kind::Required | Kind::Optional(_) => quote!(self.#ident.clone()),
+            Kind::Default(None) => quote!(self.#ident.clone().unwrap_or_default()),
+            Kind::Default(Some(default)) => {
+                quote!(self.#ident.clone().unwrap_or_else(|| #default))
+            }
+        }
+    }
+}
+
+fn builder_attrs(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attrs.iter().filter(|attr| attr.path().is_ident("builder"))
+}
+
+// T of an Option<T>
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if path.qself.is_some() || segment.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    match arguments.args.first() {
+        Some(GenericArgument::Type(inner)) if arguments.args.len() == 1 => Some(inner),
+        _ => None,
+    }
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream2> {
+    if !input.generics.params.is_empty() {
+        let message = "#[derive(Builder)] doesn't support generic structs";
+        return Err(Error::new(input.generics.span(), message));
+    }
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    input.span(),
+                    "expected a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                "#[derive(Builder)] only supports structs",
+            ))
+        }
+    };
+    let fields = fields
+        .iter()
+        .map(Field::parse)
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut validate: Option<Path> = None;
+    let mut error: Option<Type> = None;
+    for attr in builder_attrs(&input.attrs) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("validate") {
+                validate = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("error") {
+                error = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `validate = ...` or `error = ...`"))
+            }
+        })?;
+    }
+
+    let vis = &input.vis;
+    let target = &input.ident;
+    let builder = format_ident!("{}Builder", target);
+
+    let stored_idents = fields.iter().map(|field| &field.ident);
+    let stored_types = fields.iter().map(Field::stored);
+    let required: Vec<&Field> = fields
+        .iter()
+        .filter(|field| matches!(field.kind, Kind::Required))
+        .collect();
+    let arguments = required.iter().map(|field| {
+        let (ident, ty) = (&field.ident, &field.ty);
+        quote!(#ident: #ty)
+    });
+    let initial = fields.iter().map(|field| {
+        let ident = &field.ident;
+        match field.kind {
+            Kind::Required => quote!(#ident),
+            _ => quote!(#ident: ::core::option::Option::None),
+        }
+    });
+    let setters = fields.iter().filter_map(Field::setter);
+    let values = fields.iter().map(|field| {
+        let (ident, value) = (&field.ident, field.value());
+        quote!(#ident: #value)
+    });
+    let construct = quote! {
+        #target {
+            #(#values,)*
+        }
+    };
+
+    let build = match (validate, error) {
+        (Some(validate), Some(error)) => quote! {
+            pub fn build(&mut self) -> ::core::result::Result<#target, #error> {
+                #validate(self)?;
+                ::core::result::Result::Ok(#construct)
+            }
+        },
+        (None, None) => quote! {
+            pub fn build(&mut self) -> #target {
+                #construct
+            }
+        },
+        _ => {
+            let message = "`validate` and `error` go together, set both or neither";
+            return Err(Error::new(input.ident.span(), message));
+        }
+    };
+
+    // new() without arguments would trip clippy::new_without_default
+    let default = required.is_empty().then(|| {
+        quote! {
+            impl ::core::default::Default for #builder {
+                fn default() -> Self {
+                    Self::new()
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        #vis struct #builder {
+            #(#stored_idents: #stored_types,)*
+        }
+
+        impl #builder {
+            pub fn new(#(#arguments),*) -> #builder {
+                #builder {
+                    #(#initial,)*
+                }
+            }
+
+            #(#setters)*
+
+            #build
+        }
+
+        #default
+    })
+}
@@ -0,0 +1,76 @@
+use design_patterns_derive::Builder;
+
+#[derive(Debug, PartialEq, Builder)]
+pub struct Job {
+    name: String,
+    #[builder(default)]
+    retries: u32,
+    #[builder(default = 30)]
+    timeout_secs: u64,
+    #[builder(default = vec!["default".to_string()])]
+    queues: Vec<String>,
+    comment: Option<String>,
+}
+
+#[test]
+fn required_fields_go_through_new_and_the_rest_have_defaults() {
+    let job = JobBuilder::new("backup".to_string()).build();
+
+    assert_eq!(
+        job,
+        Job {
+            name: "backup".to_string(),
+            retries: 0,
+            timeout_secs: 30,
+            queues: vec!["default".to_string()],
+            comment: None,
+        }
+    );
+}
+
+#[test]
+fn setters_chain_and_override_the_defaults() {
+    let mut builder = JobBuilder::new("backup".to_string());
+    builder
+        .retries(3)
+        .timeout_secs(60)
+        .comment("nightly".to_string());
+    let first = builder.build();
+    // Building doesn't consume the builder
+    let second = builder.queues(Vec::new()).build();
+
+    assert_eq!(first.retries, 3);
+    assert_eq!(first.timeout_secs, 60);
+    assert_eq!(first.comment.as_deref(), Some("nightly"));
+    assert_eq!(first.queues, ["default"]);
+    assert!(second.queues.is_empty());
+}
+
+#[derive(Debug, PartialEq)]
+pub struct PortError(u16);
+
+#[derive(Debug, Builder)]
+#[builder(validate = ListenerBuilder::validate, error = PortError)]
+pub struct Listener {
+    #[builder(default = 8080)]
+    port: u16,
+}
+
+impl ListenerBuilder {
+    fn validate(&self) -> Result<(), PortError> {
+        match self.port {
+            Some(port) if port < 1024 => Err(PortError(port)),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[test]
+fn validation_hook_runs_before_build() {
+    // No required fields, so the builder has a Default too
+    let listener = ListenerBuilder::default().build().unwrap();
+    assert_eq!(listener.port, 8080);
+
+    let err = ListenerBuilder::new().port(80).build().unwrap_err();
+    assert_eq!(err, PortError(80));
+}
@@ -0,0 +1,51 @@
+// Example Animal plugin
+// Build it with `cargo build -p cow-plugin` and load the resulting libcow_plugin.so with
+// design_patterns::creational::factory::plugin::load_plugins_from_dir().
+
+use std::io::{self, Write};
+
+use design_patterns::creational::factory::{Animal, AnimalFactory};
+
+pub struct Cow;
+
+impl Animal for Cow {
+    fn speak(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Cow says: Moo!")
+    }
+
+    fn kind(&self) -> &str {
+        "cow"
+    }
+}
+
+pub struct Sheep;
+
+impl Animal for Sheep {
+    fn speak(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Sheep says: Baa!")
+    }
+
+    fn kind(&self) -> &str {
+        "sheep"
+    }
+}
+
+#[cfg_attr(cow_plugin_without_declaration, allow(dead_code))]
+fn register(factory: &mut AnimalFactory) {
+    factory
+        .register("cow", || Box::new(Cow))
+        .register("sheep", || Box::new(Sheep));
+}
+
+#[cfg(not(any(cow_plugin_incompatible_abi, cow_plugin_without_declaration)))]
+design_patterns::export_animal_plugin!(register);
+
+#[cfg(cow_plugin_incompatible_abi)]
+#[no_mangle]
+pub static DESIGN_PATTERNS_ANIMAL_PLUGIN:
+    design_patterns::creational::factory::plugin::PluginDeclaration =
+    design_patterns::creational::factory::plugin::PluginDeclaration {
+        abi_version: 0,
+        core_version: design_patterns::creational::factory::plugin::CORE_VERSION.as_ptr(),
+        register,
+    };
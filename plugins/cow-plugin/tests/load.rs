@@ -0,0 +1,122 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use design_patterns::creational::factory::plugin::{self, PluginError, PLUGIN_ABI_VERSION};
+use design_patterns::creational::factory::AnimalFactory;
+
+// The test binary lives next to the cdylib cargo built for this package
+fn plugin_dir() -> PathBuf {
+    std::env::current_exe()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .to_path_buf()
+}
+
+fn plugin_file(dir: &Path) -> PathBuf {
+    dir.join(format!(
+        "{}cow_plugin.{}",
+        std::env::consts::DLL_PREFIX,
+        std::env::consts::DLL_EXTENSION
+    ))
+}
+
+#[test]
+fn plugin_animals_are_registered() {
+    let mut factory = AnimalFactory::new();
+    let loaded = unsafe { plugin::load_plugin(&mut factory, &plugin_file(&plugin_dir())) }.unwrap();
+
+    assert_eq!(loaded.animals, ["cow", "sheep"]);
+    assert_eq!(
+        factory.names().collect::<Vec<_>>(),
+        ["cat", "cow", "dog", "sheep"]
+    );
+
+    let mut out = Vec::new();
+    factory.create("sheep").unwrap().speak(&mut out).unwrap();
+    assert_eq!(out, b"Sheep says: Baa!\n");
+}
+
+#[test]
+fn plugins_are_loaded_from_a_directory() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("plugin-dir");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::copy(plugin_file(&plugin_dir()), plugin_file(&dir)).unwrap();
+    std::fs::write(dir.join("README.txt"), "not a plugin").unwrap();
+
+    let mut factory = AnimalFactory::empty();
+    let loaded = unsafe { plugin::load_plugins_from_dir(&mut factory, &dir) }.unwrap();
+
+    assert_eq!(loaded.len(), 1);
+    assert!(factory.is_registered("cow"));
+}
+
+// Builds a variant of the plugin into a target directory of its own and returns its path
+fn build_variant(cfg: &str) -> PathBuf {
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join(cfg);
+    let status = Command::new(env!("CARGO"))
+        .args([
+            "build",
+            "--quiet",
+            "--package",
+            "cow-plugin",
+            "--target-dir",
+        ])
+        .arg(&target_dir)
+        .env("RUSTFLAGS", format!("--cfg {cfg}"))
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+    plugin_file(&target_dir.join("debug"))
+}
+
+#[test]
+fn incompatible_plugin_is_rejected() {
+    let path = build_variant("cow_plugin_incompatible_abi");
+    let mut factory = AnimalFactory::empty();
+    let err = unsafe { plugin::load_plugin(&mut factory, &path) }.unwrap_err();
+
+    match err {
+        PluginError::IncompatibleAbi {
+            expected, found, ..
+        } => {
+            assert_eq!(expected, PLUGIN_ABI_VERSION);
+            assert_eq!(found, 0);
+        }
+        err => panic!("unexpected error: {err}"),
+    }
+    assert_eq!(factory.names().count(), 0);
+}
+
+#[test]
+fn library_without_declaration_is_rejected() {
+    let path = build_variant("cow_plugin_without_declaration");
+    let mut factory = AnimalFactory::empty();
+    let err = unsafe { plugin::load_plugin(&mut factory, &path) }.unwrap_err();
+
+    assert!(matches!(err, PluginError::MissingDeclaration { .. }));
+    assert_eq!(factory.names().count(), 0);
+}
+
+#[test]
+fn failing_directory_registers_nothing() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("failing-plugin-dir");
+    std::fs::create_dir_all(&dir).unwrap();
+    // The good plugin comes first in file name order
+    std::fs::copy(plugin_file(&plugin_dir()), plugin_file(&dir)).unwrap();
+    std::fs::copy(
+        build_variant("cow_plugin_without_declaration"),
+        dir.join(format!(
+            "zz-not-a-plugin.{}",
+            std::env::consts::DLL_EXTENSION
+        )),
+    )
+    .unwrap();
+
+    let mut factory = AnimalFactory::new();
+    let err = unsafe { plugin::load_plugins_from_dir(&mut factory, &dir) }.unwrap_err();
+
+    assert!(matches!(err, PluginError::MissingDeclaration { .. }));
+    assert_eq!(factory.names().collect::<Vec<_>>(), ["cat", "dog"]);
+}
@@ -0,0 +1,106 @@
+// Behavioral Pattern - Chain of Responsibility
+// It allows passing request along the chain of potential handlers to handle a request generated
+// by a client.
+// Can scale well as we just have to had new independent handler and implement Department trait
+// without touching exisiting code (More flexible, less rigid)
+
+use std::io::{self, Write};
+
+#[derive(Default)]
+pub struct User {
+    pub order_placed: bool,
+    pub payment_done: bool,
+    pub delivered: bool,
+}
+
+pub trait Department {
+    fn execute(&mut self, user: &mut User, out: &mut dyn Write) -> io::Result<()> {
+        self.handle(user, out)?;
+        if let Some(next) = &mut self.next() {
+            next.execute(user, out)?;
+        }
+        Ok(())
+    }
+
+    fn handle(&mut self, user: &mut User, out: &mut dyn Write) -> io::Result<()>;
+
+    fn next(&mut self) -> &mut Option<Box<dyn Department>>;
+}
+
+#[derive(Default)]
+pub struct Order {
+    pub next: Option<Box<dyn Department>>,
+}
+
+impl Department for Order {
+    fn handle(&mut self, user: &mut User, out: &mut dyn Write) -> io::Result<()> {
+        if user.order_placed {
+            writeln!(out, "Order is already placed")
+        } else {
+            user.order_placed = true;
+            writeln!(out, "Order placed")
+        }
+    }
+
+    fn next(&mut self) -> &mut Option<Box<dyn Department>> {
+        &mut self.next
+    }
+}
+
+#[derive(Default)]
+pub struct PaymentA {
+    pub next: Option<Box<dyn Department>>,
+}
+
+impl Department for PaymentA {
+    fn handle(&mut self, user: &mut User, out: &mut dyn Write) -> io::Result<()> {
+        if user.payment_done {
+            writeln!(out, "Payment is already done")
+        } else {
+            user.payment_done = true;
+            writeln!(out, "Payment done")
+        }
+    }
+
+    fn next(&mut self) -> &mut Option<Box<dyn Department>> {
+        &mut self.next
+    }
+}
+
+#[derive(Default)]
+pub struct DeliveryA {
+    pub next: Option<Box<dyn Department>>,
+}
+
+impl Department for DeliveryA {
+    fn handle(&mut self, user: &mut User, out: &mut dyn Write) -> io::Result<()> {
+        if user.delivered {
+            writeln!(out, "Delivery is already done")
+        } else {
+            user.delivered = true;
+            writeln!(out, "Delivered")
+        }
+    }
+
+    fn next(&mut self) -> &mut Option<Box<dyn Department>> {
+        &mut self.next
+    }
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let mut user = User::default();
+
+    let delivery = DeliveryA { next: None };
+    let payment = PaymentA {
+        next: Some(Box::new(delivery)),
+    };
+    let mut order = Order {
+        next: Some(Box::new(payment)),
+    };
+
+    order.execute(&mut user, out)?;
+
+    writeln!(out, "\nThe Order has been already handled:\n")?;
+
+    order.execute(&mut user, out)
+}
@@ -0,0 +1,98 @@
+// Behavioral Pattern - Command
+// This pattern helps you combine the commands into an object and provide an unified function to
+// call the command
+
+// Each action is encapsulated into a struct with the trait Command
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+pub trait Command {
+    fn execute(&self, out: &mut dyn Write) -> io::Result<()>;
+}
+
+#[derive(Copy, Clone, Default)]
+pub struct TV;
+
+impl TV {
+    pub fn new() -> TV {
+        TV
+    }
+
+    pub fn on(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "TV is on, watch movies.")
+    }
+
+    pub fn off(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "TV is off")
+    }
+}
+
+pub struct TVOnCommand {
+    tv: TV,
+}
+
+impl TVOnCommand {
+    pub fn new(tv: TV) -> TVOnCommand {
+        TVOnCommand { tv }
+    }
+}
+
+impl Command for TVOnCommand {
+    fn execute(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.tv.on(out)
+    }
+}
+
+pub struct TVOffCommand {
+    tv: TV,
+}
+
+impl TVOffCommand {
+    pub fn new(tv: TV) -> TVOffCommand {
+        TVOffCommand { tv }
+    }
+}
+
+impl Command for TVOffCommand {
+    fn execute(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.tv.off(out)
+    }
+}
+
+#[derive(Default)]
+pub struct TVRemoteControl {
+    commands: HashMap<i32, Box<dyn Command>>,
+}
+
+impl TVRemoteControl {
+    pub fn new() -> TVRemoteControl {
+        TVRemoteControl {
+            commands: HashMap::new(),
+        }
+    }
+
+    pub fn set_command(&mut self, idx: i32, cmd: Box<dyn Command>) {
+        self.commands.insert(idx, cmd);
+    }
+
+    pub fn press_button(&self, idx: i32, out: &mut dyn Write) -> io::Result<()> {
+        if let Some(cmd) = self.commands.get(&idx) {
+            cmd.execute(out)
+        } else {
+            writeln!(out, "do nothing.")
+        }
+    }
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let tv = TV::new();
+    let mut remote_control = TVRemoteControl::new();
+    remote_control.press_button(0, out)?;
+
+    remote_control.set_command(1, Box::new(TVOnCommand::new(tv)));
+    remote_control.set_command(2, Box::new(TVOffCommand::new(tv)));
+
+    remote_control.press_button(1, out)?;
+    remote_control.press_button(2, out)
+}
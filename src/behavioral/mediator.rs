@@ -0,0 +1,68 @@
+// Behavioral Pattern - Mediator
+// Mediator is a behavioral design pattern that reduces coupling between components of a program
+// by making them communicate indirectly, through a special mediator object.
+// A user gets the mediator object by reference and never talks to other users directly.
+
+use std::io::{self, Write};
+
+pub struct User {
+    name: String,
+}
+
+impl User {
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn send(
+        &self,
+        mediator: &dyn ChatMediator,
+        message: &str,
+        out: &mut dyn Write,
+    ) -> io::Result<()> {
+        writeln!(out, "{} sends: {}", self.name, message)?;
+        mediator.send_message(&self.name, message, out)
+    }
+
+    pub fn receive(&self, message: &str, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "{} received: {}", self.name, message)
+    }
+}
+
+pub trait ChatMediator {
+    fn add_user(&mut self, user: User);
+    fn send_message(&self, sender: &str, message: &str, out: &mut dyn Write) -> io::Result<()>;
+}
+
+#[derive(Default)]
+pub struct ChatMediatorImpl {
+    users: Vec<User>,
+}
+
+impl ChatMediator for ChatMediatorImpl {
+    fn add_user(&mut self, user: User) {
+        self.users.push(user);
+    }
+
+    // Deliver the message to every user except the sender
+    fn send_message(&self, sender: &str, message: &str, out: &mut dyn Write) -> io::Result<()> {
+        for user in self.users.iter().filter(|user| user.name != sender) {
+            user.receive(message, out)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let mut mediator = ChatMediatorImpl::default();
+    mediator.add_user(User::new("Alice".to_string()));
+    mediator.add_user(User::new("Bob".to_string()));
+    mediator.add_user(User::new("Carol".to_string()));
+
+    let dave = User::new("Dave".to_string());
+    dave.send(&mediator, "Hi all!", out)
+}
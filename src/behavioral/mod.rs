@@ -0,0 +1,17 @@
+// Behavioral patterns deal with the communication and collaboration between objects to achieve
+// specific behaviors.
+
+#[cfg(feature = "chain-of-responsibility")]
+pub mod chain_of_responsibility;
+#[cfg(feature = "command")]
+pub mod command;
+#[cfg(feature = "iterator")]
+pub mod iterator;
+#[cfg(feature = "mediator")]
+pub mod mediator;
+#[cfg(feature = "observer")]
+pub mod observer;
+#[cfg(feature = "state")]
+pub mod state;
+#[cfg(feature = "strategy")]
+pub mod strategy;
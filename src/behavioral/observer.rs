@@ -0,0 +1,64 @@
+// Behavioral Pattern - Observer (PubSub)
+// The Observer design pattern allows for a loosely coupled relationship between objects. The
+// subject (observable) maintains a list of observers and notifies them when changes occur,
+// without having explicit knowledge of the observers' concrete implementations. This promotes
+// modularity and enables a flexible notification mechanism.
+
+use std::io::{self, Write};
+
+pub trait YoutubeSubscriber {
+    fn send_notification(&self, event: String, out: &mut dyn Write) -> io::Result<()>;
+}
+
+pub struct YoutubeChannel {
+    name: String,
+    subscribers: Vec<Box<dyn YoutubeSubscriber>>,
+}
+
+impl YoutubeChannel {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            subscribers: Vec::new(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn subscribe(&mut self, subscriber: Box<dyn YoutubeSubscriber>) {
+        self.subscribers.push(subscriber);
+    }
+
+    pub fn notify(&self, event: String, out: &mut dyn Write) -> io::Result<()> {
+        for sub in &self.subscribers {
+            sub.send_notification(event.clone(), out)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct YoutubeUser {
+    pub name: String,
+}
+
+impl YoutubeSubscriber for YoutubeUser {
+    fn send_notification(&self, event: String, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "User {} received notification: {}", self.name, event)
+    }
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let mut channel = YoutubeChannel::new("funtime".to_string());
+    channel.subscribe(Box::new(YoutubeUser {
+        name: "sub1".to_string(),
+    }));
+    channel.subscribe(Box::new(YoutubeUser {
+        name: "sub2".to_string(),
+    }));
+    channel.subscribe(Box::new(YoutubeUser {
+        name: "sub3".to_string(),
+    }));
+    channel.notify("New video uploaded".to_string(), out)
+}
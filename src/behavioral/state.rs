@@ -0,0 +1,153 @@
+// Behavioral Pattern - State
+// State lets an object alter its behavior when its internal state changes. Each state is its own
+// type implementing the State trait, and the object (Post) delegates to whichever state it holds.
+// Adding a new state doesn't touch the existing ones, and invalid transitions are simply no-ops.
+
+use std::io::{self, Write};
+
+pub trait State {
+    fn request_review(self: Box<Self>) -> Box<dyn State>;
+    fn approve(self: Box<Self>) -> Box<dyn State>;
+    fn reject(self: Box<Self>) -> Box<dyn State>;
+    fn name(&self) -> &'static str;
+
+    // Only a published post shows its content
+    fn content<'a>(&self, _post: &'a Post) -> &'a str {
+        ""
+    }
+}
+
+pub struct Draft;
+
+impl State for Draft {
+    fn request_review(self: Box<Self>) -> Box<dyn State> {
+        Box::new(PendingReview)
+    }
+
+    fn approve(self: Box<Self>) -> Box<dyn State> {
+        self
+    }
+
+    fn reject(self: Box<Self>) -> Box<dyn State> {
+        self
+    }
+
+    fn name(&self) -> &'static str {
+        "Draft"
+    }
+}
+
+pub struct PendingReview;
+
+impl State for PendingReview {
+    fn request_review(self: Box<Self>) -> Box<dyn State> {
+        self
+    }
+
+    fn approve(self: Box<Self>) -> Box<dyn State> {
+        Box::new(Published)
+    }
+
+    fn reject(self: Box<Self>) -> Box<dyn State> {
+        Box::new(Draft)
+    }
+
+    fn name(&self) -> &'static str {
+        "PendingReview"
+    }
+}
+
+pub struct Published;
+
+impl State for Published {
+    fn request_review(self: Box<Self>) -> Box<dyn State> {
+        self
+    }
+
+    fn approve(self: Box<Self>) -> Box<dyn State> {
+        self
+    }
+
+    fn reject(self: Box<Self>) -> Box<dyn State> {
+        self
+    }
+
+    fn name(&self) -> &'static str {
+        "Published"
+    }
+
+    fn content<'a>(&self, post: &'a Post) -> &'a str {
+        &post.content
+    }
+}
+
+pub struct Post {
+    // Always Some, the Option only lets us move the state out while transitioning
+    state: Option<Box<dyn State>>,
+    content: String,
+}
+
+impl Post {
+    pub fn new() -> Post {
+        Post {
+            state: Some(Box::new(Draft)),
+            content: String::new(),
+        }
+    }
+
+    pub fn add_text(&mut self, text: &str) {
+        self.content.push_str(text);
+    }
+
+    pub fn content(&self) -> &str {
+        self.state().content(self)
+    }
+
+    pub fn state_name(&self) -> &'static str {
+        self.state().name()
+    }
+
+    pub fn request_review(&mut self) {
+        self.transition(|state| state.request_review());
+    }
+
+    pub fn approve(&mut self) {
+        self.transition(|state| state.approve());
+    }
+
+    pub fn reject(&mut self) {
+        self.transition(|state| state.reject());
+    }
+
+    fn state(&self) -> &dyn State {
+        self.state.as_deref().expect("post always has a state")
+    }
+
+    fn transition(&mut self, f: impl FnOnce(Box<dyn State>) -> Box<dyn State>) {
+        if let Some(state) = self.state.take() {
+            self.state = Some(f(state));
+        }
+    }
+}
+
+impl Default for Post {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let mut post = Post::new();
+    post.add_text("I ate a salad for lunch today");
+    writeln!(out, "{}: {:?}", post.state_name(), post.content())?;
+
+    post.request_review();
+    writeln!(out, "{}: {:?}", post.state_name(), post.content())?;
+
+    post.reject();
+    writeln!(out, "{}: {:?}", post.state_name(), post.content())?;
+
+    post.request_review();
+    post.approve();
+    writeln!(out, "{}: {:?}", post.state_name(), post.content())
+}
@@ -0,0 +1,41 @@
+// Behavioral Pattern - Strategy
+// This example demonstrates how the Strategy pattern allows for interchangeable algorithms by
+// encapsulating them in separate strategy objects and providing a unified interface (FilterStrategy)
+// for using different strategies interchangeably.
+
+use std::io::{self, Write};
+
+pub trait FilterStrategy {
+    fn remove_value(&self, val: i32) -> bool;
+}
+
+pub struct RemoveNegativeStrategy;
+
+impl FilterStrategy for RemoveNegativeStrategy {
+    fn remove_value(&self, val: i32) -> bool {
+        val >= 0
+    }
+}
+
+pub struct RemoveOddStrategy;
+
+impl FilterStrategy for RemoveOddStrategy {
+    fn remove_value(&self, val: i32) -> bool {
+        val % 2 == 0
+    }
+}
+
+#[derive(Debug)]
+pub struct Values(pub Vec<i32>);
+
+impl Values {
+    pub fn filter(&mut self, strategy: impl FilterStrategy) {
+        self.0.retain(|val| strategy.remove_value(*val));
+    }
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let mut values = Values(vec![-1, 3, 2, 4, -5]);
+    values.filter(RemoveNegativeStrategy);
+    writeln!(out, "{values:?}")
+}
@@ -0,0 +1,112 @@
+// `patterns` runs the demo of every pattern compiled into the library.
+//
+//     patterns list [category]      lists each pattern with its category and description
+//     patterns search <keyword>     lists the patterns whose name or description mention keyword
+//     patterns info <pattern>       shows the catalog entry of a single pattern
+//     patterns run <pattern>        runs the demo of a single pattern, e.g. `patterns run proxy`
+//     patterns markdown             prints the README pattern tables
+
+use std::io::{self, Write};
+use std::process::ExitCode;
+
+use design_patterns::catalog::{self, Category, PatternInfo};
+
+const USAGE: &str = "Usage:
+    patterns list [category]     List every available pattern, optionally of one category
+    patterns search <keyword>    List the patterns matching a keyword
+    patterns info <pattern>      Show the details of a pattern
+    patterns run <pattern>       Run the demo of a pattern
+    patterns markdown            Print the README pattern tables";
+
+fn print_table<'a>(
+    out: &mut dyn Write,
+    patterns: impl IntoIterator<Item = &'a PatternInfo>,
+) -> io::Result<ExitCode> {
+    let patterns: Vec<_> = patterns.into_iter().collect();
+    let name_width = patterns
+        .iter()
+        .map(|pattern| pattern.name.len())
+        .max()
+        .unwrap_or(0);
+
+    for pattern in patterns {
+        writeln!(
+            out,
+            "{:<name_width$}  {:<10}  {}",
+            pattern.name, pattern.category, pattern.intent
+        )?;
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn find(name: &str) -> Result<&'static PatternInfo, ExitCode> {
+    catalog::find(name).ok_or_else(|| {
+        eprintln!("Unknown pattern `{name}`. Run `patterns list` to see the available patterns.");
+        ExitCode::from(2)
+    })
+}
+
+fn list(out: &mut dyn Write, category: Option<&str>) -> io::Result<ExitCode> {
+    match category {
+        None => print_table(out, catalog::patterns()),
+        Some(name) => match Category::from_name(name) {
+            Some(category) => print_table(out, catalog::by_category(category)),
+            None => {
+                eprintln!(
+                    "Unknown category `{name}`. Expected one of creational, structural, behavioral."
+                );
+                Ok(ExitCode::from(2))
+            }
+        },
+    }
+}
+
+fn info(out: &mut dyn Write, name: &str) -> io::Result<ExitCode> {
+    let pattern = match find(name) {
+        Ok(pattern) => pattern,
+        Err(code) => return Ok(code),
+    };
+
+    writeln!(out, "{} ({})", pattern.title, pattern.category)?;
+    writeln!(out, "{}", pattern.intent)?;
+    writeln!(out, "Related: {}", pattern.related.join(", "))?;
+    writeln!(out, "Source:  {}", pattern.source)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+fn run(out: &mut dyn Write, name: &str) -> io::Result<ExitCode> {
+    match find(name) {
+        Ok(pattern) => pattern.run_demo(out).map(|()| ExitCode::SUCCESS),
+        Err(code) => Ok(code),
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let mut stdout = io::stdout().lock();
+    let code = match args.as_slice() {
+        ["list"] => list(&mut stdout, None),
+        ["list", category] => list(&mut stdout, Some(category)),
+        ["search", keyword] => print_table(&mut stdout, catalog::search(keyword)),
+        ["info", name] => info(&mut stdout, name),
+        ["run", name] => run(&mut stdout, name),
+        ["markdown"] => catalog::write_markdown(&mut stdout).map(|()| ExitCode::SUCCESS),
+        ["help" | "-h" | "--help"] => writeln!(stdout, "{USAGE}").map(|()| ExitCode::SUCCESS),
+        _ => {
+            eprintln!("{USAGE}");
+            Ok(ExitCode::from(2))
+        }
+    };
+
+    match code.and_then(|code| stdout.flush().map(|()| code)) {
+        Ok(code) => code,
+        // Whoever reads the output has stopped, e.g. `patterns list | head -n 3`
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Failed to write the output: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
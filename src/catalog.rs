@@ -0,0 +1,319 @@
+// Catalog of every pattern compiled into the library.
+// Each entry carries the metadata shown in the README tables along with a runnable demo, so the
+// CLI and any docs tooling can query the patterns instead of keeping their own copy in sync.
+
+use std::fmt;
+use std::io::{self, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Category {
+    Creational,
+    Structural,
+    Behavioral,
+}
+
+impl Category {
+    pub const ALL: [Category; 3] = [
+        Category::Creational,
+        Category::Structural,
+        Category::Behavioral,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Category::Creational => "creational",
+            Category::Structural => "structural",
+            Category::Behavioral => "behavioral",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Category> {
+        Category::ALL
+            .into_iter()
+            .find(|category| category.name().eq_ignore_ascii_case(name))
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+pub struct PatternInfo {
+    // Identifier used by the CLI and the cargo feature of the pattern, e.g. "chain-of-responsibility"
+    pub name: &'static str,
+    // Human readable name, e.g. "Chain of Responsibility"
+    pub title: &'static str,
+    pub category: Category,
+    pub intent: &'static str,
+    // Names of related patterns in this catalog
+    pub related: &'static [&'static str],
+    // Path of the implementation relative to the crate root
+    pub source: &'static str,
+    pub demo: fn(&mut dyn Write) -> io::Result<()>,
+}
+
+impl PatternInfo {
+    pub fn run_demo(&self, out: &mut dyn Write) -> io::Result<()> {
+        (self.demo)(out)
+    }
+
+    // Case-insensitive match against the name, title and intent
+    pub fn matches(&self, keyword: &str) -> bool {
+        let keyword = keyword.to_lowercase();
+        [self.name, self.title, self.intent]
+            .iter()
+            .any(|field| field.to_lowercase().contains(&keyword))
+    }
+}
+
+impl fmt::Debug for PatternInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PatternInfo")
+            .field("name", &self.name)
+            .field("title", &self.title)
+            .field("category", &self.category)
+            .field("intent", &self.intent)
+            .field("related", &self.related)
+            .field("source", &self.source)
+            .finish_non_exhaustive()
+    }
+}
+
+const PATTERNS: &[PatternInfo] = &[
+    #[cfg(feature = "factory")]
+    PatternInfo {
+        name: "factory",
+        title: "Factory Method",
+        category: Category::Creational,
+        intent: "Defers instantiation of an object to a specialized function for creating instances",
+        related: &["abstract-factory", "prototype"],
+        source: "src/creational/factory.rs",
+        demo: crate::creational::factory::demo,
+    },
+    #[cfg(feature = "abstract-factory")]
+    PatternInfo {
+        name: "abstract-factory",
+        title: "Abstract Factory",
+        category: Category::Creational,
+        intent: "Provides an interface for creating families of related objects",
+        related: &["factory", "builder", "singleton"],
+        source: "src/creational/abstract_factory.rs",
+        demo: crate::creational::abstract_factory::demo,
+    },
+    #[cfg(feature = "builder")]
+    PatternInfo {
+        name: "builder",
+        title: "Builder",
+        category: Category::Creational,
+        intent: "Builds a complex object using simple objects",
+        related: &["abstract-factory", "composite"],
+        source: "src/creational/builder.rs",
+        demo: crate::creational::builder::demo,
+    },
+    #[cfg(feature = "singleton")]
+    PatternInfo {
+        name: "singleton",
+        title: "Singleton",
+        category: Category::Creational,
+        intent: "Restricts instantiation of a type to one object",
+        related: &["abstract-factory", "facade", "flyweight"],
+        source: "src/creational/singleton.rs",
+        demo: crate::creational::singleton::demo,
+    },
+    #[cfg(feature = "prototype")]
+    PatternInfo {
+        name: "prototype",
+        title: "Prototype",
+        category: Category::Creational,
+        intent: "Creates new objects by copying an existing object",
+        related: &["factory", "composite", "decorator"],
+        source: "src/creational/prototype.rs",
+        demo: crate::creational::prototype::demo,
+    },
+    #[cfg(feature = "dependency-injection")]
+    PatternInfo {
+        name: "dependency-injection",
+        title: "Dependency Injection",
+        category: Category::Creational,
+        intent: "Resolves a graph of dependencies from registered providers instead of wiring them by hand",
+        related: &["factory", "abstract-factory", "singleton", "facade"],
+        source: "src/creational/dependency_injection.rs",
+        demo: crate::creational::dependency_injection::demo,
+    },
+    #[cfg(feature = "adapter")]
+    PatternInfo {
+        name: "adapter",
+        title: "Adapter",
+        category: Category::Structural,
+        intent: "Allows objects with incompatible interfaces to collaborate",
+        related: &["decorator", "facade", "proxy"],
+        source: "src/structural/adapter.rs",
+        demo: crate::structural::adapter::demo,
+    },
+    #[cfg(feature = "composite")]
+    PatternInfo {
+        name: "composite",
+        title: "Composite",
+        category: Category::Structural,
+        intent: "Lets you compose objects into tree structures",
+        related: &["decorator", "iterator", "flyweight"],
+        source: "src/structural/composite.rs",
+        demo: crate::structural::composite::demo,
+    },
+    #[cfg(feature = "decorator")]
+    PatternInfo {
+        name: "decorator",
+        title: "Decorator",
+        category: Category::Structural,
+        intent: "Adds behavior to an object, statically or dynamically",
+        related: &["adapter", "composite", "proxy", "strategy"],
+        source: "src/structural/decorator.rs",
+        demo: crate::structural::decorator::demo,
+    },
+    #[cfg(feature = "facade")]
+    PatternInfo {
+        name: "facade",
+        title: "Facade",
+        category: Category::Structural,
+        intent: "Provides a simple interface before a complex system",
+        related: &["adapter", "mediator", "singleton"],
+        source: "src/structural/facade.rs",
+        demo: crate::structural::facade::demo,
+    },
+    #[cfg(feature = "flyweight")]
+    PatternInfo {
+        name: "flyweight",
+        title: "FlyWeight",
+        category: Category::Structural,
+        intent: "Lets you fit more objects into the available RAM by sharing common parts of state between multiple objects",
+        related: &["composite", "singleton"],
+        source: "src/structural/flyweight.rs",
+        demo: crate::structural::flyweight::demo,
+    },
+    #[cfg(feature = "proxy")]
+    PatternInfo {
+        name: "proxy",
+        title: "Proxy",
+        category: Category::Structural,
+        intent: "Provides a surrogate for an object to control its actions",
+        related: &["adapter", "decorator"],
+        source: "src/structural/proxy.rs",
+        demo: crate::structural::proxy::demo,
+    },
+    #[cfg(feature = "strategy")]
+    PatternInfo {
+        name: "strategy",
+        title: "Strategy",
+        category: Category::Behavioral,
+        intent: "Enables an algorithm's behavior to be selected at runtime",
+        related: &["state", "decorator", "command"],
+        source: "src/behavioral/strategy.rs",
+        demo: crate::behavioral::strategy::demo,
+    },
+    #[cfg(feature = "state")]
+    PatternInfo {
+        name: "state",
+        title: "State",
+        category: Category::Behavioral,
+        intent: "Encapsulates varying behavior for the same object based on its internal state",
+        related: &["strategy", "singleton"],
+        source: "src/behavioral/state.rs",
+        demo: crate::behavioral::state::demo,
+    },
+    #[cfg(feature = "command")]
+    PatternInfo {
+        name: "command",
+        title: "Command",
+        category: Category::Behavioral,
+        intent: "Converts requests or simple operations into objects",
+        related: &["chain-of-responsibility", "strategy", "prototype"],
+        source: "src/behavioral/command.rs",
+        demo: crate::behavioral::command::demo,
+    },
+    #[cfg(feature = "iterator")]
+    PatternInfo {
+        name: "iterator",
+        title: "Iterator",
+        category: Category::Behavioral,
+        intent: "Lets you traverse elements of a collection without exposing its underlying representation",
+        related: &["composite", "factory"],
+        source: "src/behavioral/iterator.rs",
+        demo: crate::behavioral::iterator::demo,
+    },
+    #[cfg(feature = "observer")]
+    PatternInfo {
+        name: "observer",
+        title: "Observer",
+        category: Category::Behavioral,
+        intent: "Allows one object to notify other objects about changes in their state",
+        related: &["mediator", "chain-of-responsibility"],
+        source: "src/behavioral/observer.rs",
+        demo: crate::behavioral::observer::demo,
+    },
+    #[cfg(feature = "chain-of-responsibility")]
+    PatternInfo {
+        name: "chain-of-responsibility",
+        title: "Chain of Responsibility",
+        category: Category::Behavioral,
+        intent: "Avoids coupling a sender to receiver by giving more than one object a chance to handle the request",
+        related: &["command", "composite", "decorator"],
+        source: "src/behavioral/chain_of_responsibility.rs",
+        demo: crate::behavioral::chain_of_responsibility::demo,
+    },
+    #[cfg(feature = "mediator")]
+    PatternInfo {
+        name: "mediator",
+        title: "Mediator",
+        category: Category::Behavioral,
+        intent: "Reduces coupling between objects by making them communicate through a mediator object",
+        related: &["facade", "observer"],
+        source: "src/behavioral/mediator.rs",
+        demo: crate::behavioral::mediator::demo,
+    },
+];
+
+// Every pattern enabled through cargo features, in README order
+pub fn patterns() -> &'static [PatternInfo] {
+    PATTERNS
+}
+
+pub fn find(name: &str) -> Option<&'static PatternInfo> {
+    PATTERNS.iter().find(|pattern| pattern.name == name)
+}
+
+pub fn by_category(category: Category) -> impl Iterator<Item = &'static PatternInfo> {
+    PATTERNS
+        .iter()
+        .filter(move |pattern| pattern.category == category)
+}
+
+pub fn search(keyword: &str) -> impl Iterator<Item = &'static PatternInfo> + '_ {
+    PATTERNS
+        .iter()
+        .filter(move |pattern| pattern.matches(keyword))
+}
+
+// Renders the README tables, one per category, linking each pattern to its source file
+pub fn write_markdown(out: &mut dyn Write) -> io::Result<()> {
+    for category in Category::ALL {
+        let title = match category {
+            Category::Creational => "Creational Patterns",
+            Category::Structural => "Structural Patterns",
+            Category::Behavioral => "Behavioral Patterns",
+        };
+        writeln!(out, "## {title}\n")?;
+        writeln!(out, "| Pattern | Description |")?;
+        writeln!(out, "|:-------:|:----------- |")?;
+        for pattern in by_category(category) {
+            writeln!(
+                out,
+                "| [{}]({}) | {} |",
+                pattern.title, pattern.source, pattern.intent
+            )?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,328 @@
+// Creational Pattern - Abstract Factory
+// The Abstract Factory Pattern is used to create families of related objects. It consists of two
+// main components: the abstract factory(GUIFactory) and the concrete factory(WinFactory,
+// LinuxFactory). The abstract factory defines the interface for creating a family of products,
+// while the concrete factory implements this interface to create specific products.
+//
+// GUIFactory hands out trait objects, which is what runtime selection of a family needs, but it
+// also lets a caller mix a WinButton with a LinuxMenu. GUIFamily expresses the family through
+// associated types instead: a Window<F> only accepts the products of family F, so mixing families
+// is a compile error, and everything is statically dispatched. Every GUIFamily is a GUIFactory too.
+//
+// Besides the WinFactory and LinuxFactory labels, the ansi and html families really render: boxes
+// and colours on a terminal, or a self-contained HTML page. The ui module composes the products
+// of any family into a widget tree with layout and event dispatch. The resolver module picks a
+// family from config, the environment or the terminal, so callers don't have to.
+
+pub mod ansi;
+pub mod html;
+pub mod resolver;
+pub mod ui;
+
+use std::io::{self, Write};
+
+pub trait GUIFactory {
+    fn create_button(&self) -> Box<dyn Button>;
+    fn create_menu(&self) -> Box<dyn Menu>;
+    fn create_textbox(&self) -> Box<dyn Textbox>;
+
+    // Written before and after the widgets of a window, e.g. the HTML document around them
+    fn begin_window(&self, _out: &mut dyn Write) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn end_window(&self, _out: &mut dyn Write) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+pub trait Button {
+    fn paint(&self, out: &mut dyn Write) -> io::Result<()>;
+
+    // Families that don't show a label ignore it
+    fn set_label(&mut self, _label: &str) {}
+}
+
+pub trait Menu {
+    fn display(&self, out: &mut dyn Write) -> io::Result<()>;
+}
+
+pub trait Textbox {
+    fn text(&self) -> String;
+    fn set_text(&mut self, text: String);
+
+    fn paint(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "{}", self.text())
+    }
+}
+
+// What the rendering families put on their widgets
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Labels {
+    pub button: String,
+    pub menu: Vec<String>,
+    pub textbox: String,
+}
+
+impl Default for Labels {
+    fn default() -> Self {
+        Self {
+            button: "OK".to_string(),
+            menu: vec!["File".to_string(), "Edit".to_string(), "Help".to_string()],
+            textbox: "Type here".to_string(),
+        }
+    }
+}
+
+pub trait GUIFamily {
+    type Button: Button + 'static;
+    type Menu: Menu + 'static;
+    type Textbox: Textbox + 'static;
+
+    fn button(&self) -> Self::Button;
+    fn menu(&self) -> Self::Menu;
+    fn textbox(&self) -> Self::Textbox;
+
+    fn begin_window(&self, _out: &mut dyn Write) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn end_window(&self, _out: &mut dyn Write) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<F: GUIFamily> GUIFactory for F {
+    fn create_button(&self) -> Box<dyn Button> {
+        Box::new(self.button())
+    }
+
+    fn create_menu(&self) -> Box<dyn Menu> {
+        Box::new(self.menu())
+    }
+
+    fn create_textbox(&self) -> Box<dyn Textbox> {
+        Box::new(self.textbox())
+    }
+
+    fn begin_window(&self, out: &mut dyn Write) -> io::Result<()> {
+        GUIFamily::begin_window(self, out)
+    }
+
+    fn end_window(&self, out: &mut dyn Write) -> io::Result<()> {
+        GUIFamily::end_window(self, out)
+    }
+}
+
+pub struct WinFactory {}
+
+impl GUIFamily for WinFactory {
+    type Button = WinButton;
+    type Menu = WinMenu;
+    type Textbox = WinTextbox;
+
+    fn button(&self) -> WinButton {
+        WinButton {}
+    }
+
+    fn menu(&self) -> WinMenu {
+        WinMenu {}
+    }
+
+    fn textbox(&self) -> WinTextbox {
+        WinTextbox::default()
+    }
+}
+
+pub struct LinuxFactory {}
+
+impl GUIFamily for LinuxFactory {
+    type Button = LinuxButton;
+    type Menu = LinuxMenu;
+    type Textbox = LinuxTextbox;
+
+    fn button(&self) -> LinuxButton {
+        LinuxButton {}
+    }
+
+    fn menu(&self) -> LinuxMenu {
+        LinuxMenu {}
+    }
+
+    fn textbox(&self) -> LinuxTextbox {
+        LinuxTextbox::default()
+    }
+}
+
+pub struct WinButton {}
+
+impl Button for WinButton {
+    fn paint(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Windows Button")
+    }
+}
+
+pub struct LinuxButton {}
+
+impl Button for LinuxButton {
+    fn paint(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Linux Button")
+    }
+}
+
+pub struct WinMenu {}
+
+impl Menu for WinMenu {
+    fn display(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Windows Menu")
+    }
+}
+
+pub struct LinuxMenu {}
+
+impl Menu for LinuxMenu {
+    fn display(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Linux Menu")
+    }
+}
+
+pub struct WinTextbox {
+    text: String,
+}
+
+impl Default for WinTextbox {
+    fn default() -> Self {
+        Self {
+            text: String::from("Windows Textbox"),
+        }
+    }
+}
+
+impl Textbox for WinTextbox {
+    fn text(&self) -> String {
+        self.text.clone()
+    }
+
+    fn set_text(&mut self, text: String) {
+        self.text = text;
+    }
+}
+
+pub struct LinuxTextbox {
+    text: String,
+}
+
+impl Default for LinuxTextbox {
+    fn default() -> Self {
+        Self {
+            text: String::from("Linux Textbox"),
+        }
+    }
+}
+
+impl Textbox for LinuxTextbox {
+    fn text(&self) -> String {
+        self.text.clone()
+    }
+
+    fn set_text(&mut self, text: String) {
+        self.text = text;
+    }
+}
+
+// Factory method
+pub fn create_gui(factory: &dyn GUIFactory, out: &mut dyn Write) -> io::Result<()> {
+    let button = factory.create_button();
+    let menu = factory.create_menu();
+    let textbox = factory.create_textbox();
+
+    factory.begin_window(out)?;
+    button.paint(out)?;
+    menu.display(out)?;
+    textbox.paint(out)?;
+    factory.end_window(out)
+}
+
+// Products of a single family, checked at compile time
+pub struct Window<F: GUIFamily> {
+    pub family: F,
+    pub button: F::Button,
+    pub menu: F::Menu,
+    pub textbox: F::Textbox,
+}
+
+impl<F: GUIFamily> Window<F> {
+    pub fn new(family: F, button: F::Button, menu: F::Menu, textbox: F::Textbox) -> Self {
+        Self {
+            family,
+            button,
+            menu,
+            textbox,
+        }
+    }
+
+    pub fn from_family(family: F) -> Self {
+        let (button, menu, textbox) = (family.button(), family.menu(), family.textbox());
+        Self::new(family, button, menu, textbox)
+    }
+
+    pub fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        GUIFamily::begin_window(&self.family, out)?;
+        self.button.paint(out)?;
+        self.menu.display(out)?;
+        self.textbox.paint(out)?;
+        GUIFamily::end_window(&self.family, out)
+    }
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let win_factory = WinFactory {};
+    let linux_factory = LinuxFactory {};
+
+    create_gui(&win_factory, out)?;
+    create_gui(&linux_factory, out)?;
+
+    // Statically dispatched, Window::new(LinuxFactory {}, WinButton {}, ..) wouldn't compile
+    let window = Window::from_family(linux_factory);
+    window.render(out)?;
+
+    // A family that really renders, boxes and colours on an ANSI terminal
+    create_gui(&ansi::AnsiFactory::default(), out)?;
+
+    // Family picked at runtime, here from config, among built-in and third-party families
+    let mut resolver = resolver::FamilyResolver::new();
+    resolver.register("high-contrast", || {
+        let theme = ansi::AnsiTheme {
+            border: ansi::Color::White,
+            label: ansi::Color::Yellow,
+            menu_foreground: ansi::Color::White,
+            menu_background: ansi::Color::Black,
+        };
+        Box::new(ansi::AnsiFactory::new(Labels::default(), theme))
+    });
+    let resolution = resolver
+        .resolve(Some("high-contrast"))
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+    writeln!(
+        out,
+        "Resolved GUI family: {} (from {})",
+        resolution.name, resolution.source
+    )?;
+    create_gui(resolution.factory.as_ref(), out)?;
+
+    // Products laid out in a widget tree and driven by scripted events
+    let mut ui = ui::Ui::new(Box::new(LinuxFactory {}));
+    let name = ui.add_textbox("name", "");
+    let greet = ui.add_button("greet", "Greet");
+    ui.on_click(greet, move |ui| {
+        let text = format!(
+            "Hello, {}!",
+            ui.textbox(name).map(|t| t.text()).unwrap_or_default()
+        );
+        ui.set_text(name, text);
+    });
+    ui.set_root(ui::Stack::vertical().spacing(1).child(name).child(greet));
+    ui.run_script("click name\ntype Rust\ntab\nenter")
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+    write!(out, "{}", ui.render_canvas())
+}
@@ -0,0 +1,184 @@
+// ANSI terminal family
+// Draws every widget with box-drawing characters and SGR colour escapes, so the output looks like
+// a UI on any ANSI capable terminal. Labels are escaped before they are painted, a newline or an
+// escape sequence in one would break the box or take over the terminal, and boxes are as wide as
+// their label is on the terminal, which isn't its number of chars for CJK or combining characters.
+
+use std::io::{self, Write};
+
+use unicode_width::UnicodeWidthStr;
+
+use super::{Button, GUIFamily, Labels, Menu, Textbox};
+
+const RESET: &str = "\x1b[0m";
+
+// SGR colour codes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Color {
+    fn foreground(&self) -> u8 {
+        30 + *self as u8
+    }
+
+    fn background(&self) -> u8 {
+        40 + *self as u8
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnsiTheme {
+    pub border: Color,
+    pub label: Color,
+    pub menu_foreground: Color,
+    pub menu_background: Color,
+}
+
+impl Default for AnsiTheme {
+    fn default() -> Self {
+        Self {
+            border: Color::Blue,
+            label: Color::White,
+            menu_foreground: Color::Black,
+            menu_background: Color::Cyan,
+        }
+    }
+}
+
+// Control characters are written as Rust escapes, e.g. \n or \u{1b}
+pub fn escape(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            if c.is_control() {
+                c.escape_default().to_string()
+            } else {
+                c.to_string()
+            }
+        })
+        .collect()
+}
+
+fn paint_box(out: &mut dyn Write, theme: &AnsiTheme, content: &str, bold: bool) -> io::Result<()> {
+    let content = escape(content);
+    let border = format!("\x1b[{}m", theme.border.foreground());
+    let label = format!(
+        "\x1b[{}{}m",
+        if bold { "1;" } else { "" },
+        theme.label.foreground()
+    );
+    let width = content.width() + 2;
+    let line = "─".repeat(width);
+
+    writeln!(out, "{border}┌{line}┐{RESET}")?;
+    writeln!(
+        out,
+        "{border}│{RESET}{label} {content} {RESET}{border}│{RESET}"
+    )?;
+    writeln!(out, "{border}└{line}┘{RESET}")
+}
+
+#[derive(Debug, Clone)]
+pub struct AnsiButton {
+    label: String,
+    theme: AnsiTheme,
+}
+
+impl Button for AnsiButton {
+    fn paint(&self, out: &mut dyn Write) -> io::Result<()> {
+        paint_box(out, &self.theme, &format!("[ {} ]", self.label), true)
+    }
+
+    fn set_label(&mut self, label: &str) {
+        self.label = label.to_string();
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AnsiMenu {
+    items: Vec<String>,
+    theme: AnsiTheme,
+}
+
+impl Menu for AnsiMenu {
+    fn display(&self, out: &mut dyn Write) -> io::Result<()> {
+        let items: String = self
+            .items
+            .iter()
+            .map(|item| format!(" {} ", escape(item)))
+            .collect();
+        writeln!(
+            out,
+            "\x1b[{};{}m{items}{RESET}",
+            self.theme.menu_foreground.foreground(),
+            self.theme.menu_background.background()
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AnsiTextbox {
+    text: String,
+    theme: AnsiTheme,
+}
+
+impl Textbox for AnsiTextbox {
+    fn text(&self) -> String {
+        self.text.clone()
+    }
+
+    fn set_text(&mut self, text: String) {
+        self.text = text;
+    }
+
+    fn paint(&self, out: &mut dyn Write) -> io::Result<()> {
+        paint_box(out, &self.theme, &self.text, false)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AnsiFactory {
+    pub labels: Labels,
+    pub theme: AnsiTheme,
+}
+
+impl AnsiFactory {
+    pub fn new(labels: Labels, theme: AnsiTheme) -> Self {
+        Self { labels, theme }
+    }
+}
+
+impl GUIFamily for AnsiFactory {
+    type Button = AnsiButton;
+    type Menu = AnsiMenu;
+    type Textbox = AnsiTextbox;
+
+    fn button(&self) -> AnsiButton {
+        AnsiButton {
+            label: self.labels.button.clone(),
+            theme: self.theme,
+        }
+    }
+
+    fn menu(&self) -> AnsiMenu {
+        AnsiMenu {
+            items: self.labels.menu.clone(),
+            theme: self.theme,
+        }
+    }
+
+    fn textbox(&self) -> AnsiTextbox {
+        AnsiTextbox {
+            text: self.labels.textbox.clone(),
+            theme: self.theme,
+        }
+    }
+}
@@ -0,0 +1,146 @@
+// HTML family
+// Renders a window as a self-contained HTML page with inline CSS, one element per widget.
+
+use std::io::{self, Write};
+
+use super::{Button, GUIFamily, Labels, Menu, Textbox};
+
+const STYLE: &str = "\
+body { font-family: sans-serif; background: #f0f0f0; }
+.window { display: inline-flex; flex-direction: column; gap: 8px; padding: 12px; background: #fff; border: 1px solid #999; border-radius: 4px; }
+.menu ul { display: flex; gap: 12px; margin: 0; padding: 4px 8px; list-style: none; background: #dde; }
+.button { padding: 4px 16px; border: 1px solid #446; border-radius: 3px; background: #eef; }
+.textbox { padding: 4px; border: 1px solid #999; }";
+
+// Escapes text for use in element content and quoted attribute values
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[derive(Debug, Clone)]
+pub struct HtmlButton {
+    label: String,
+}
+
+impl Button for HtmlButton {
+    fn paint(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(
+            out,
+            "<button class=\"button\" type=\"button\">{}</button>",
+            escape(&self.label)
+        )
+    }
+
+    fn set_label(&mut self, label: &str) {
+        self.label = label.to_string();
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HtmlMenu {
+    items: Vec<String>,
+}
+
+impl Menu for HtmlMenu {
+    fn display(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "<nav class=\"menu\"><ul>")?;
+        for item in &self.items {
+            writeln!(out, "<li>{}</li>", escape(item))?;
+        }
+        writeln!(out, "</ul></nav>")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HtmlTextbox {
+    text: String,
+}
+
+impl Textbox for HtmlTextbox {
+    fn text(&self) -> String {
+        self.text.clone()
+    }
+
+    fn set_text(&mut self, text: String) {
+        self.text = text;
+    }
+
+    fn paint(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(
+            out,
+            "<input class=\"textbox\" type=\"text\" value=\"{}\">",
+            escape(&self.text)
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HtmlFactory {
+    pub title: String,
+    pub labels: Labels,
+}
+
+impl HtmlFactory {
+    pub fn new(title: String, labels: Labels) -> Self {
+        Self { title, labels }
+    }
+}
+
+impl Default for HtmlFactory {
+    fn default() -> Self {
+        Self::new("Design Patterns".to_string(), Labels::default())
+    }
+}
+
+impl GUIFamily for HtmlFactory {
+    type Button = HtmlButton;
+    type Menu = HtmlMenu;
+    type Textbox = HtmlTextbox;
+
+    fn button(&self) -> HtmlButton {
+        HtmlButton {
+            label: self.labels.button.clone(),
+        }
+    }
+
+    fn menu(&self) -> HtmlMenu {
+        HtmlMenu {
+            items: self.labels.menu.clone(),
+        }
+    }
+
+    fn textbox(&self) -> HtmlTextbox {
+        HtmlTextbox {
+            text: self.labels.textbox.clone(),
+        }
+    }
+
+    fn begin_window(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "<!DOCTYPE html>")?;
+        writeln!(out, "<html lang=\"en\">")?;
+        writeln!(out, "<head>")?;
+        writeln!(out, "<meta charset=\"utf-8\">")?;
+        writeln!(out, "<title>{}</title>", escape(&self.title))?;
+        writeln!(out, "<style>\n{STYLE}\n</style>")?;
+        writeln!(out, "</head>")?;
+        writeln!(out, "<body>")?;
+        writeln!(out, "<div class=\"window\">")
+    }
+
+    fn end_window(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "</div>")?;
+        writeln!(out, "</body>")?;
+        writeln!(out, "</html>")
+    }
+}
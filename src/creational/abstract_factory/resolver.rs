@@ -0,0 +1,264 @@
+// Family resolver
+// Picks the GUIFactory family at runtime instead of making the caller choose one by hand. Families
+// are registered by name, like animals in the AnimalFactory, so other crates can add their own,
+// e.g. a high-contrast theme. The first of these that gives a name wins:
+//
+//     1. the explicit config value passed to resolve()
+//     2. the DESIGN_PATTERNS_GUI_FAMILY environment variable
+//     3. terminal capability detection, the most recently registered detector is asked first
+//     4. the fallback family set with set_fallback(), otherwise "windows" when the capabilities
+//        say the OS is Windows and "linux" everywhere else
+//
+// A config value or environment variable that names an unknown family is an error rather than a
+// silent fall through, so a typo doesn't go unnoticed. Empty values count as unset.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::io::IsTerminal;
+
+use super::ansi::AnsiFactory;
+use super::html::HtmlFactory;
+use super::{GUIFactory, LinuxFactory, WinFactory};
+
+pub const GUI_FAMILY_ENV: &str = "DESIGN_PATTERNS_GUI_FAMILY";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorSupport {
+    None,
+    Basic,
+    TrueColor,
+}
+
+// What the resolver knows about the terminal it runs in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capabilities {
+    pub is_terminal: bool,
+    pub colors: ColorSupport,
+    // As in std::env::consts::OS, picks the default fallback family
+    pub os: String,
+}
+
+impl Capabilities {
+    // Looks at stdout, NO_COLOR, COLORTERM and TERM
+    pub fn detect() -> Self {
+        let var = |name| std::env::var(name).ok().filter(|value| !value.is_empty());
+        let colors = if var("NO_COLOR").is_some() {
+            ColorSupport::None
+        } else if matches!(var("COLORTERM").as_deref(), Some("truecolor" | "24bit")) {
+            ColorSupport::TrueColor
+        } else {
+            match var("TERM").as_deref() {
+                None | Some("dumb") => ColorSupport::None,
+                Some(_) => ColorSupport::Basic,
+            }
+        };
+
+        Self {
+            is_terminal: std::io::stdout().is_terminal(),
+            colors,
+            os: std::env::consts::OS.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    Config,
+    Environment,
+    Detected,
+    Fallback,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let source = match self {
+            Source::Config => "config",
+            Source::Environment => "environment",
+            Source::Detected => "terminal detection",
+            Source::Fallback => "fallback",
+        };
+        f.write_str(source)
+    }
+}
+
+pub type FamilyConstructor = Box<dyn Fn() -> Box<dyn GUIFactory> + Send + Sync>;
+pub type Detector = Box<dyn Fn(&Capabilities) -> bool + Send + Sync>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FamilyError {
+    UnknownFamily { name: String, source: Source },
+    NoFamily,
+}
+
+impl fmt::Display for FamilyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FamilyError::UnknownFamily { name, source } => {
+                write!(
+                    f,
+                    "no GUI family is registered under `{name}` (from {source})"
+                )
+            }
+            FamilyError::NoFamily => {
+                write!(f, "no GUI family matched and no fallback is registered")
+            }
+        }
+    }
+}
+
+impl Error for FamilyError {}
+
+pub struct Resolution {
+    pub name: String,
+    pub source: Source,
+    pub factory: Box<dyn GUIFactory>,
+}
+
+impl fmt::Debug for Resolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Resolution")
+            .field("name", &self.name)
+            .field("source", &self.source)
+            .finish_non_exhaustive()
+    }
+}
+
+pub struct FamilyResolver {
+    constructors: BTreeMap<String, FamilyConstructor>,
+    // In registration order, asked from the back
+    detectors: Vec<(String, Detector)>,
+    // None picks the family of Capabilities::os
+    fallback: Option<String>,
+}
+
+impl FamilyResolver {
+    // Resolver with the built-in families, "windows", "linux", "ansi" (detected on colour
+    // terminals) and "html"
+    pub fn new() -> Self {
+        let mut resolver = Self::empty();
+        resolver
+            .register("windows", || Box::new(WinFactory {}))
+            .register("linux", || Box::new(LinuxFactory {}))
+            .register("html", || Box::new(HtmlFactory::default()))
+            .register("ansi", || Box::new(AnsiFactory::default()))
+            .detect("ansi", |capabilities| {
+                capabilities.is_terminal && capabilities.colors != ColorSupport::None
+            });
+        resolver
+    }
+
+    pub fn empty() -> Self {
+        Self {
+            constructors: BTreeMap::new(),
+            detectors: Vec::new(),
+            fallback: None,
+        }
+    }
+
+    // Registering a name twice replaces the previous constructor
+    pub fn register<F>(&mut self, name: impl Into<String>, constructor: F) -> &mut Self
+    where
+        F: Fn() -> Box<dyn GUIFactory> + Send + Sync + 'static,
+    {
+        self.constructors.insert(name.into(), Box::new(constructor));
+        self
+    }
+
+    // Picks the family when the terminal matches, ahead of every detector registered before
+    pub fn detect<F>(&mut self, name: impl Into<String>, detector: F) -> &mut Self
+    where
+        F: Fn(&Capabilities) -> bool + Send + Sync + 'static,
+    {
+        self.detectors.push((name.into(), Box::new(detector)));
+        self
+    }
+
+    // Used whatever the OS is
+    pub fn set_fallback(&mut self, name: impl Into<String>) -> &mut Self {
+        self.fallback = Some(name.into());
+        self
+    }
+
+    pub fn fallback(&self, capabilities: &Capabilities) -> &str {
+        match &self.fallback {
+            Some(name) => name,
+            None if capabilities.os == "windows" => "windows",
+            None => "linux",
+        }
+    }
+
+    // Also drops the detectors of the family
+    pub fn unregister(&mut self, name: &str) -> Option<FamilyConstructor> {
+        self.detectors.retain(|(family, _)| family != name);
+        self.constructors.remove(name)
+    }
+
+    pub fn is_registered(&self, name: &str) -> bool {
+        self.constructors.contains_key(name)
+    }
+
+    // Registered names in alphabetical order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.constructors.keys().map(String::as_str)
+    }
+
+    pub fn create(&self, name: &str) -> Option<Box<dyn GUIFactory>> {
+        self.constructors.get(name).map(|constructor| constructor())
+    }
+
+    // Resolves against the process environment and the detected terminal
+    pub fn resolve(&self, config: Option<&str>) -> Result<Resolution, FamilyError> {
+        let env = std::env::var(GUI_FAMILY_ENV).ok();
+        self.resolve_with(config, env.as_deref(), &Capabilities::detect())
+    }
+
+    pub fn resolve_with(
+        &self,
+        config: Option<&str>,
+        env: Option<&str>,
+        capabilities: &Capabilities,
+    ) -> Result<Resolution, FamilyError> {
+        let explicit = [(config, Source::Config), (env, Source::Environment)];
+        for (value, source) in explicit {
+            let Some(name) = value.map(str::trim).filter(|name| !name.is_empty()) else {
+                continue;
+            };
+            if !self.is_registered(name) {
+                let name = name.to_string();
+                return Err(FamilyError::UnknownFamily { name, source });
+            }
+            return Ok(self.resolution(name, source));
+        }
+
+        let detected = self
+            .detectors
+            .iter()
+            .rev()
+            .find(|(name, detector)| self.is_registered(name) && detector(capabilities));
+        if let Some((name, _)) = detected {
+            return Ok(self.resolution(name, Source::Detected));
+        }
+
+        let fallback = self.fallback(capabilities);
+        if self.is_registered(fallback) {
+            Ok(self.resolution(fallback, Source::Fallback))
+        } else {
+            Err(FamilyError::NoFamily)
+        }
+    }
+
+    fn resolution(&self, name: &str, source: Source) -> Resolution {
+        Resolution {
+            name: name.to_string(),
+            source,
+            factory: self.constructors[name](),
+        }
+    }
+}
+
+impl Default for FamilyResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,663 @@
+// Widget tree
+// A Ui composes the products of any GUIFactory into a tree of vertical and horizontal stacks. The
+// layout engine measures every widget from what it paints, in character cells, and places it
+// inside its stack (padding, spacing, optional fixed sizes). Synthetic events are routed to the
+// widgets: a click focuses the widget under it, keys go to the focused widget and Tab moves the
+// focus. Events can also be scripted, which lets a headless test drive the whole UI.
+//
+//     click name       clicks the middle of the widget called name
+//     click 3 4        clicks the cell at column 3, row 4
+//     type some text   types every character of the rest of the line
+//     key c            types a single character
+//     tab | backtab | enter | backspace
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+
+use super::{Button, GUIFactory, Menu, Textbox};
+
+// Handed out by the add_*() methods and can't be made up, so an id given back to the Ui that
+// returned it always names one of its widgets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WidgetId(usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Size {
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Size {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self { width, height }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rect {
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Rect {
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        // Subtracting keeps a rect at the edge of the u16 range from overflowing
+        x >= self.x && x - self.x < self.width && y >= self.y && y - self.y < self.height
+    }
+
+    pub fn center(&self) -> (u16, u16) {
+        (
+            self.x.saturating_add(self.width / 2),
+            self.y.saturating_add(self.height / 2),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Vertical,
+    Horizontal,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Layout {
+    Widget(WidgetId),
+    Stack(Stack),
+}
+
+impl From<WidgetId> for Layout {
+    fn from(id: WidgetId) -> Self {
+        Layout::Widget(id)
+    }
+}
+
+impl From<Stack> for Layout {
+    fn from(stack: Stack) -> Self {
+        Layout::Stack(stack)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stack {
+    pub direction: Direction,
+    // Empty cells around the children
+    pub padding: u16,
+    // Empty cells between two children
+    pub spacing: u16,
+    pub children: Vec<Layout>,
+}
+
+impl Stack {
+    pub fn new(direction: Direction) -> Self {
+        Self {
+            direction,
+            padding: 0,
+            spacing: 0,
+            children: Vec::new(),
+        }
+    }
+
+    pub fn vertical() -> Self {
+        Self::new(Direction::Vertical)
+    }
+
+    pub fn horizontal() -> Self {
+        Self::new(Direction::Horizontal)
+    }
+
+    pub fn padding(mut self, padding: u16) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    pub fn spacing(mut self, spacing: u16) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    pub fn child(mut self, child: impl Into<Layout>) -> Self {
+        self.children.push(child.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Char(char),
+    Backspace,
+    Enter,
+    Tab,
+    BackTab,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Click { x: u16, y: u16 },
+    Key(Key),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptError {
+    // 1-based line of the script
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for ScriptError {}
+
+pub type ClickHandler = Box<dyn FnMut(&mut Ui)>;
+
+enum Widget {
+    Button {
+        button: Box<dyn Button>,
+        on_click: Option<ClickHandler>,
+    },
+    Menu(Box<dyn Menu>),
+    Textbox(Box<dyn Textbox>),
+}
+
+impl Widget {
+    fn paint(&self, out: &mut dyn Write) -> io::Result<()> {
+        match self {
+            Widget::Button { button, .. } => button.paint(out),
+            Widget::Menu(menu) => menu.display(out),
+            Widget::Textbox(textbox) => textbox.paint(out),
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        !matches!(self, Widget::Menu(_))
+    }
+}
+
+struct Entry {
+    name: String,
+    widget: Widget,
+    // Overrides the measured size
+    fixed_size: Option<Size>,
+    rect: Rect,
+}
+
+// Size of a layout node and of each of its children, measure() builds it once per layout pass so
+// arrange() never has to measure (and paint) a widget again
+struct Measured {
+    size: Size,
+    children: Vec<Measured>,
+}
+
+pub struct Ui {
+    factory: Box<dyn GUIFactory>,
+    widgets: Vec<Entry>,
+    root: Layout,
+    focus: Option<WidgetId>,
+    size: Size,
+}
+
+impl Ui {
+    pub fn new(factory: Box<dyn GUIFactory>) -> Self {
+        Self {
+            factory,
+            widgets: Vec::new(),
+            root: Layout::Stack(Stack::vertical()),
+            focus: None,
+            size: Size::default(),
+        }
+    }
+
+    fn add(&mut self, name: &str, widget: Widget) -> WidgetId {
+        self.widgets.push(Entry {
+            name: name.to_string(),
+            widget,
+            fixed_size: None,
+            rect: Rect::default(),
+        });
+        WidgetId(self.widgets.len() - 1)
+    }
+
+    pub fn add_button(&mut self, name: &str, label: &str) -> WidgetId {
+        let mut button = self.factory.create_button();
+        button.set_label(label);
+        self.add(
+            name,
+            Widget::Button {
+                button,
+                on_click: None,
+            },
+        )
+    }
+
+    pub fn add_menu(&mut self, name: &str) -> WidgetId {
+        let menu = self.factory.create_menu();
+        self.add(name, Widget::Menu(menu))
+    }
+
+    pub fn add_textbox(&mut self, name: &str, text: &str) -> WidgetId {
+        let mut textbox = self.factory.create_textbox();
+        textbox.set_text(text.to_string());
+        self.add(name, Widget::Textbox(textbox))
+    }
+
+    // Called when the button is clicked or Enter is pressed while it has the focus
+    pub fn on_click(&mut self, id: WidgetId, handler: impl FnMut(&mut Ui) + 'static) {
+        if let Widget::Button { on_click, .. } = &mut self.widgets[id.0].widget {
+            *on_click = Some(Box::new(handler));
+        }
+    }
+
+    pub fn set_fixed_size(&mut self, id: WidgetId, size: Size) {
+        self.widgets[id.0].fixed_size = Some(size);
+        self.layout();
+    }
+
+    pub fn set_root(&mut self, root: impl Into<Layout>) {
+        self.root = root.into();
+        self.layout();
+    }
+
+    pub fn find(&self, name: &str) -> Option<WidgetId> {
+        self.widgets
+            .iter()
+            .position(|entry| entry.name == name)
+            .map(WidgetId)
+    }
+
+    pub fn name(&self, id: WidgetId) -> &str {
+        &self.widgets[id.0].name
+    }
+
+    pub fn rect(&self, id: WidgetId) -> Rect {
+        self.widgets[id.0].rect
+    }
+
+    // Size of the whole tree after the last layout
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    pub fn focus(&self) -> Option<WidgetId> {
+        self.focus
+    }
+
+    pub fn set_focus(&mut self, id: Option<WidgetId>) {
+        self.focus = id.filter(|id| self.widgets[id.0].widget.is_focusable());
+    }
+
+    pub fn textbox(&self, id: WidgetId) -> Option<&dyn Textbox> {
+        match &self.widgets[id.0].widget {
+            Widget::Textbox(textbox) => Some(textbox.as_ref()),
+            _ => None,
+        }
+    }
+
+    pub fn set_text(&mut self, id: WidgetId, text: String) {
+        if let Widget::Textbox(textbox) = &mut self.widgets[id.0].widget {
+            textbox.set_text(text);
+            self.layout();
+        }
+    }
+
+    // Measures every widget and places it, returns the size of the whole tree
+    pub fn layout(&mut self) -> Size {
+        let root = std::mem::replace(&mut self.root, Layout::Stack(Stack::vertical()));
+        let measured = self.measure(&root);
+        self.arrange(&root, &measured, 0, 0);
+        self.root = root;
+        self.size = measured.size;
+        measured.size
+    }
+
+    fn measure_widget(&self, id: WidgetId) -> Size {
+        let entry = &self.widgets[id.0];
+        if let Some(size) = entry.fixed_size {
+            return size;
+        }
+
+        let mut painted = Vec::new();
+        // Painting into a Vec can't fail
+        let _ = entry.widget.paint(&mut painted);
+        let painted = String::from_utf8_lossy(&painted);
+        let width = painted.lines().map(visible_width).max().unwrap_or(0);
+        let size = Size::new(to_cells(width), to_cells(painted.lines().count()));
+        // An empty textbox still needs a cell to be clicked on
+        match entry.widget {
+            Widget::Textbox(_) => Size::new(size.width.max(1), size.height.max(1)),
+            _ => size,
+        }
+    }
+
+    fn measure(&self, layout: &Layout) -> Measured {
+        let stack = match layout {
+            Layout::Widget(id) => {
+                return Measured {
+                    size: self.measure_widget(*id),
+                    children: Vec::new(),
+                }
+            }
+            Layout::Stack(stack) => stack,
+        };
+
+        let children: Vec<Measured> = stack
+            .children
+            .iter()
+            .map(|child| self.measure(child))
+            .collect();
+        let sizes = children.iter().map(|child| child.size);
+        // Sizes saturate at u16::MAX cells, a huge widget must not overflow its stack
+        let gaps = stack
+            .spacing
+            .saturating_mul(to_cells(children.len().saturating_sub(1)));
+        let (width, height) = match stack.direction {
+            Direction::Vertical => (
+                sizes.clone().map(|size| size.width).max().unwrap_or(0),
+                saturating_sum(sizes.map(|size| size.height)).saturating_add(gaps),
+            ),
+            Direction::Horizontal => (
+                saturating_sum(sizes.clone().map(|size| size.width)).saturating_add(gaps),
+                sizes.map(|size| size.height).max().unwrap_or(0),
+            ),
+        };
+        let padding = stack.padding.saturating_mul(2);
+        Measured {
+            size: Size::new(
+                width.saturating_add(padding),
+                height.saturating_add(padding),
+            ),
+            children,
+        }
+    }
+
+    fn arrange(&mut self, layout: &Layout, measured: &Measured, x: u16, y: u16) {
+        let stack = match layout {
+            Layout::Widget(id) => {
+                self.widgets[id.0].rect = Rect {
+                    x,
+                    y,
+                    width: measured.size.width,
+                    height: measured.size.height,
+                };
+                return;
+            }
+            Layout::Stack(stack) => stack,
+        };
+
+        let (mut x, mut y) = (
+            x.saturating_add(stack.padding),
+            y.saturating_add(stack.padding),
+        );
+        for (child, measured) in stack.children.iter().zip(&measured.children) {
+            self.arrange(child, measured, x, y);
+            match stack.direction {
+                Direction::Vertical => {
+                    y = y
+                        .saturating_add(measured.size.height)
+                        .saturating_add(stack.spacing)
+                }
+                Direction::Horizontal => {
+                    x = x
+                        .saturating_add(measured.size.width)
+                        .saturating_add(stack.spacing)
+                }
+            }
+        }
+    }
+
+    // Widgets of the tree in layout order, which is also the focus order
+    pub fn widgets_in_tree(&self) -> Vec<WidgetId> {
+        fn collect(layout: &Layout, ids: &mut Vec<WidgetId>) {
+            match layout {
+                Layout::Widget(id) => ids.push(*id),
+                Layout::Stack(stack) => {
+                    for child in &stack.children {
+                        collect(child, ids);
+                    }
+                }
+            }
+        }
+
+        let mut ids = Vec::new();
+        collect(&self.root, &mut ids);
+        ids
+    }
+
+    pub fn widget_at(&self, x: u16, y: u16) -> Option<WidgetId> {
+        self.widgets_in_tree()
+            .into_iter()
+            .find(|id| self.widgets[id.0].rect.contains(x, y))
+    }
+
+    fn click(&mut self, id: WidgetId) {
+        let handler = match &mut self.widgets[id.0].widget {
+            Widget::Button { on_click, .. } => on_click.take(),
+            _ => None,
+        };
+
+        if let Some(mut handler) = handler {
+            handler(self);
+            // The handler may have installed a new one for this button
+            if let Widget::Button { on_click, .. } = &mut self.widgets[id.0].widget {
+                on_click.get_or_insert(handler);
+            }
+        }
+    }
+
+    fn move_focus(&mut self, forward: bool) {
+        let focusable: Vec<WidgetId> = self
+            .widgets_in_tree()
+            .into_iter()
+            .filter(|id| self.widgets[id.0].widget.is_focusable())
+            .collect();
+        if focusable.is_empty() {
+            return;
+        }
+
+        let current = self
+            .focus
+            .and_then(|focus| focusable.iter().position(|id| *id == focus));
+        let next = match (current, forward) {
+            (None, true) => 0,
+            (None, false) => focusable.len() - 1,
+            (Some(index), true) => (index + 1) % focusable.len(),
+            (Some(index), false) => (index + focusable.len() - 1) % focusable.len(),
+        };
+        self.focus = Some(focusable[next]);
+    }
+
+    // Returns whether a widget handled the event
+    pub fn dispatch(&mut self, event: Event) -> bool {
+        match event {
+            Event::Click { x, y } => {
+                let target = self.widget_at(x, y);
+                self.set_focus(target);
+                match target {
+                    Some(id) => {
+                        self.click(id);
+                        true
+                    }
+                    None => false,
+                }
+            }
+            Event::Key(Key::Tab) => {
+                self.move_focus(true);
+                true
+            }
+            Event::Key(Key::BackTab) => {
+                self.move_focus(false);
+                true
+            }
+            Event::Key(key) => {
+                let Some(id) = self.focus else {
+                    return false;
+                };
+                match (&mut self.widgets[id.0].widget, key) {
+                    (Widget::Button { .. }, Key::Enter) => self.click(id),
+                    (Widget::Textbox(textbox), Key::Char(c)) => {
+                        let mut text = textbox.text();
+                        text.push(c);
+                        textbox.set_text(text);
+                    }
+                    (Widget::Textbox(textbox), Key::Backspace) => {
+                        let mut text = textbox.text();
+                        text.pop();
+                        textbox.set_text(text);
+                    }
+                    _ => return false,
+                }
+                self.layout();
+                true
+            }
+        }
+    }
+
+    pub fn run_script(&mut self, script: &str) -> Result<(), ScriptError> {
+        for (index, line) in script.lines().enumerate() {
+            let error = |message: String| ScriptError {
+                line: index + 1,
+                message,
+            };
+
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (command, argument) = line.split_once(' ').unwrap_or((line, ""));
+
+            let events = match (command, argument) {
+                ("tab", "") => vec![Event::Key(Key::Tab)],
+                ("backtab", "") => vec![Event::Key(Key::BackTab)],
+                ("enter", "") => vec![Event::Key(Key::Enter)],
+                ("backspace", "") => vec![Event::Key(Key::Backspace)],
+                ("type", text) => text.chars().map(|c| Event::Key(Key::Char(c))).collect(),
+                ("key", key) => {
+                    let mut chars = key.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(c), None) => vec![Event::Key(Key::Char(c))],
+                        _ => return Err(error(format!("`key` takes one character, got `{key}`"))),
+                    }
+                }
+                ("click", target) => {
+                    let (x, y) = self.click_target(target).map_err(error)?;
+                    vec![Event::Click { x, y }]
+                }
+                _ => return Err(error(format!("unknown command `{line}`"))),
+            };
+
+            for event in events {
+                self.dispatch(event);
+            }
+        }
+        Ok(())
+    }
+
+    fn click_target(&self, target: &str) -> Result<(u16, u16), String> {
+        if let Some((x, y)) = target.split_once(' ') {
+            let parse = |value: &str| {
+                value
+                    .trim()
+                    .parse::<u16>()
+                    .map_err(|_| format!("invalid coordinate `{value}`"))
+            };
+            return Ok((parse(x)?, parse(y)?));
+        }
+
+        match self.find(target) {
+            Some(id) => Ok(self.rect(id).center()),
+            None => Err(format!("no widget called `{target}`")),
+        }
+    }
+
+    // Paints the widgets one after another through the family, e.g. a whole HTML page
+    pub fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.factory.begin_window(out)?;
+        for id in self.widgets_in_tree() {
+            self.widgets[id.0].widget.paint(out)?;
+        }
+        self.factory.end_window(out)
+    }
+
+    // Paints every widget at its place in the layout, for families that draw in character cells
+    pub fn render_canvas(&self) -> String {
+        let mut canvas = vec![
+            vec![String::from(" "); usize::from(self.size.width)];
+            usize::from(self.size.height)
+        ];
+
+        for id in self.widgets_in_tree() {
+            let entry = &self.widgets[id.0];
+            let mut painted = Vec::new();
+            let _ = entry.widget.paint(&mut painted);
+            let painted = String::from_utf8_lossy(&painted);
+
+            let rect = entry.rect;
+            for (row, line) in painted.lines().take(usize::from(rect.height)).enumerate() {
+                let y = usize::from(rect.y) + row;
+                for (column, cell) in cells(line)
+                    .into_iter()
+                    .take(usize::from(rect.width))
+                    .enumerate()
+                {
+                    let x = usize::from(rect.x) + column;
+                    if let Some(slot) = canvas.get_mut(y).and_then(|row| row.get_mut(x)) {
+                        *slot = cell;
+                    }
+                }
+            }
+        }
+
+        canvas
+            .into_iter()
+            .map(|row| row.concat().trim_end().to_string() + "\n")
+            .collect()
+    }
+}
+
+fn to_cells(count: usize) -> u16 {
+    u16::try_from(count).unwrap_or(u16::MAX)
+}
+
+fn saturating_sum(cells: impl Iterator<Item = u16>) -> u16 {
+    cells.fold(0, u16::saturating_add)
+}
+
+// Splits a painted line into character cells. ANSI escape sequences don't take a cell, they are
+// kept with the character that follows them (or the last one, at the end of the line).
+fn cells(line: &str) -> Vec<String> {
+    let mut cells = Vec::new();
+    let mut pending = String::new();
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' && chars.peek() == Some(&'[') {
+            pending.push(c);
+            for c in chars.by_ref() {
+                pending.push(c);
+                if ('\x40'..='\x7e').contains(&c) && c != '[' {
+                    break;
+                }
+            }
+            continue;
+        }
+        pending.push(c);
+        cells.push(std::mem::take(&mut pending));
+    }
+
+    if !pending.is_empty() {
+        match cells.last_mut() {
+            Some(last) => last.push_str(&pending),
+            None => cells.push(pending),
+        }
+    }
+    cells
+}
+
+fn visible_width(line: &str) -> usize {
+    cells(line).len()
+}
@@ -0,0 +1,510 @@
+// Creational Pattern - Builder
+// The Builder design pattern allows for a more flexible and readable way of constructing complex
+// objects. It provides an interface for step-by-step construction while allowing the customization
+// of individual properties
+// Instead of creating multiple constructor with variable number of arguments, use this pattern
+//
+// build() is also the one place where the spec is validated, so every KubernetesCluster that exists
+// is a valid one. It checks every rule and reports all violations at once instead of the first.
+// The typestate module has a variant that checks the required fields at compile time, and the
+// upgrade module plans and simulates the rollout from one spec to another. The layered module fills
+// the builder from defaults, config files, environment variables and explicit calls.
+//
+// KubernetesClusterBuilder itself is generated by #[derive(Builder)] from the design-patterns-derive
+// crate: name and version are the arguments of new(), the other fields get setters.
+
+pub mod layered;
+#[cfg(feature = "manifest")]
+pub mod manifest;
+pub mod node_pool;
+pub mod typestate;
+pub mod upgrade;
+
+pub use design_patterns_derive::Builder;
+pub use node_pool::{NodePool, NodePoolBuilder, Taint, TaintEffect, MAX_POOL_NODES};
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+// Oldest and newest minor versions the builder accepts
+pub const MIN_VERSION: KubernetesVersion = KubernetesVersion::channel(1, 27);
+pub const MAX_VERSION: KubernetesVersion = KubernetesVersion::channel(1, 31);
+
+const MAX_NAME_LEN: usize = 63;
+
+// MAJOR.MINOR.PATCH, or MAJOR.MINOR for a minor-version channel that follows the latest patch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KubernetesVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: Option<u32>,
+}
+
+impl KubernetesVersion {
+    pub const fn channel(major: u32, minor: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch: None,
+        }
+    }
+
+    pub const fn release(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch: Some(patch),
+        }
+    }
+
+    pub fn is_channel(&self) -> bool {
+        self.patch.is_none()
+    }
+
+    pub fn is_supported(&self) -> bool {
+        let supported =
+            (MIN_VERSION.major, MIN_VERSION.minor)..=(MAX_VERSION.major, MAX_VERSION.minor);
+        supported.contains(&(self.major, self.minor))
+    }
+}
+
+impl fmt::Display for KubernetesVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)?;
+        match self.patch {
+            Some(patch) => write!(f, ".{patch}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromStr for KubernetesVersion {
+    // Why the version doesn't parse
+    type Err = String;
+
+    fn from_str(version: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = version.split('.').collect();
+        if !(2..=3).contains(&parts.len()) {
+            return Err("expected MAJOR.MINOR or MAJOR.MINOR.PATCH".to_string());
+        }
+
+        let mut numbers = Vec::new();
+        for part in parts {
+            let is_number = !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+            if !is_number || (part.len() > 1 && part.starts_with('0')) {
+                return Err(format!("`{part}` is not a version number"));
+            }
+            let number = part.parse().map_err(|_| format!("`{part}` is too large"))?;
+            numbers.push(number);
+        }
+
+        Ok(Self {
+            major: numbers[0],
+            minor: numbers[1],
+            patch: numbers.get(2).copied(),
+        })
+    }
+}
+
+// Why a name isn't a DNS-1123 label, None when it is one
+pub fn dns_label_error(name: &str) -> Option<&'static str> {
+    if name.is_empty() {
+        Some("must not be empty")
+    } else if name.len() > MAX_NAME_LEN {
+        Some("must be at most 63 characters")
+    } else if !name
+        .bytes()
+        .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+    {
+        Some("must consist of lowercase letters, digits and '-'")
+    } else if name.starts_with('-') || name.ends_with('-') {
+        Some("must start and end with a letter or digit")
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    InvalidName {
+        field: String,
+        value: String,
+        reason: &'static str,
+    },
+    InvalidVersion {
+        value: String,
+        reason: String,
+    },
+    UnsupportedVersion(KubernetesVersion),
+    // Auto-upgrade follows the patches of a channel, a pinned patch version would never move
+    AutoUpgradeRequiresChannel(KubernetesVersion),
+    Empty {
+        field: String,
+    },
+    InvalidAutoscaling {
+        field: String,
+        min_nodes: u32,
+        max_nodes: u32,
+    },
+    DuplicatePoolName {
+        field: String,
+        name: String,
+        // Index of the pool that has the name already
+        first: usize,
+    },
+    DuplicateZone {
+        field: String,
+        zone: String,
+    },
+}
+
+impl Violation {
+    // Field of the spec the violation is about
+    pub fn field(&self) -> &str {
+        match self {
+            Violation::InvalidName { field, .. }
+            | Violation::Empty { field }
+            | Violation::InvalidAutoscaling { field, .. }
+            | Violation::DuplicatePoolName { field, .. }
+            | Violation::DuplicateZone { field, .. } => field,
+            Violation::InvalidVersion { .. } | Violation::UnsupportedVersion(_) => "version",
+            Violation::AutoUpgradeRequiresChannel(_) => "auto_upgrade",
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.field())?;
+        match self {
+            Violation::InvalidName { value, reason, .. } => {
+                write!(f, "`{value}` is not a DNS-1123 label, it {reason}")
+            }
+            Violation::InvalidVersion { value, reason } => {
+                write!(f, "`{value}` is not a version, {reason}")
+            }
+            Violation::UnsupportedVersion(version) => {
+                write!(
+                    f,
+                    "{version} is not supported, use {MIN_VERSION} to {MAX_VERSION}"
+                )
+            }
+            Violation::AutoUpgradeRequiresChannel(version) => write!(
+                f,
+                "requires a minor-version channel such as {}.{}, not {version}",
+                version.major, version.minor
+            ),
+            Violation::Empty { .. } => write!(f, "must not be empty"),
+            Violation::InvalidAutoscaling {
+                min_nodes,
+                max_nodes,
+                ..
+            } => write!(
+                f,
+                "autoscaling from {min_nodes} to {max_nodes} nodes, max_nodes must be between \
+                 max(min_nodes, 1) and {MAX_POOL_NODES}"
+            ),
+            Violation::DuplicatePoolName { name, first, .. } => {
+                write!(f, "`{name}` is already the name of node_pools[{first}]")
+            }
+            Violation::DuplicateZone { zone, .. } => write!(f, "`{zone}` is listed twice"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClusterSpecError {
+    violations: Vec<Violation>,
+}
+
+impl ClusterSpecError {
+    // In the order the fields are checked, never empty
+    pub fn violations(&self) -> &[Violation] {
+        &self.violations
+    }
+}
+
+impl fmt::Display for ClusterSpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid cluster spec")?;
+        for violation in &self.violations {
+            write!(f, "\n  {violation}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ClusterSpecError {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
+#[builder(validate = KubernetesClusterBuilder::validate, error = ClusterSpecError)]
+pub struct KubernetesCluster {
+    name: String,
+    version: String,
+    #[builder(default)]
+    auto_upgrade: bool,
+    #[builder(default)]
+    node_pools: Vec<NodePool>,
+}
+
+impl KubernetesCluster {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    pub fn kubernetes_version(&self) -> KubernetesVersion {
+        self.version
+            .parse()
+            .expect("build() only accepts valid versions")
+    }
+
+    pub fn auto_upgrade(&self) -> bool {
+        self.auto_upgrade
+    }
+
+    pub fn node_pools(&self) -> &[NodePool] {
+        &self.node_pools
+    }
+
+    pub fn node_pool(&self, name: &str) -> Option<&NodePool> {
+        self.node_pools.iter().find(|pool| pool.name() == name)
+    }
+}
+
+impl KubernetesClusterBuilder {
+    // Adds the pool configured by a nested builder, e.g.
+    // .node_pool(NodePoolBuilder::new(name, machine_type).autoscaling(1, 5))
+    pub fn node_pool(&mut self, pool: &mut NodePoolBuilder) -> &mut Self {
+        self.node_pools
+            .get_or_insert_with(Vec::new)
+            .push(pool.build());
+        self
+    }
+
+    // Validation hook of the derived KubernetesClusterBuilder::build
+    fn validate(&self) -> Result<(), ClusterSpecError> {
+        let mut violations = Vec::new();
+        if let Some(reason) = dns_label_error(&self.name) {
+            violations.push(Violation::InvalidName {
+                field: "name".to_string(),
+                value: self.name.clone(),
+                reason,
+            });
+        }
+
+        match self.version.parse::<KubernetesVersion>() {
+            Ok(version) => {
+                if !version.is_supported() {
+                    violations.push(Violation::UnsupportedVersion(version));
+                }
+                if self.auto_upgrade == Some(true) && !version.is_channel() {
+                    violations.push(Violation::AutoUpgradeRequiresChannel(version));
+                }
+            }
+            Err(reason) => violations.push(Violation::InvalidVersion {
+                value: self.version.clone(),
+                reason,
+            }),
+        }
+
+        let pools = self.node_pools.as_deref().unwrap_or_default();
+        for (index, pool) in pools.iter().enumerate() {
+            validate_pool(index, pool, pools, &mut violations);
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ClusterSpecError { violations })
+        }
+    }
+}
+
+fn validate_pool(
+    index: usize,
+    pool: &NodePool,
+    pools: &[NodePool],
+    violations: &mut Vec<Violation>,
+) {
+    let field = |name: &str| format!("node_pools[{index}].{name}");
+
+    if let Some(reason) = dns_label_error(pool.name()) {
+        violations.push(Violation::InvalidName {
+            field: field("name"),
+            value: pool.name().to_string(),
+            reason,
+        });
+    }
+    if let Some(first) = pools[..index]
+        .iter()
+        .position(|other| other.name() == pool.name())
+    {
+        violations.push(Violation::DuplicatePoolName {
+            field: field("name"),
+            name: pool.name().to_string(),
+            first,
+        });
+    }
+
+    if pool.machine_type().trim().is_empty() {
+        violations.push(Violation::Empty {
+            field: field("machine_type"),
+        });
+    }
+
+    let (min_nodes, max_nodes) = (pool.min_nodes(), pool.max_nodes());
+    if max_nodes == 0 || min_nodes > max_nodes || max_nodes > MAX_POOL_NODES {
+        violations.push(Violation::InvalidAutoscaling {
+            field: field("max_nodes"),
+            min_nodes,
+            max_nodes,
+        });
+    }
+
+    for (position, zone) in pool.zones().iter().enumerate() {
+        if zone.trim().is_empty() {
+            violations.push(Violation::Empty {
+                field: format!("node_pools[{index}].zones[{position}]"),
+            });
+        } else if pool.zones()[..position].contains(zone) {
+            violations.push(Violation::DuplicateZone {
+                field: format!("node_pools[{index}].zones[{position}]"),
+                zone: zone.clone(),
+            });
+        }
+    }
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let name = "my-cluster".to_owned();
+    let version = "1.29.4".to_owned();
+    let channel = "1.29".to_owned();
+
+    let basic_cluster = KubernetesClusterBuilder::new(name.clone(), version.clone()).build();
+    writeln!(out, "{basic_cluster:?}")?;
+
+    let auto_upgrade_cluster = KubernetesClusterBuilder::new(name.clone(), channel.clone())
+        .auto_upgrade(true)
+        .build();
+    writeln!(out, "{auto_upgrade_cluster:?}")?;
+
+    // Separate system, compute and spot pools, each set up by its own nested builder
+    let complete_cluster = KubernetesClusterBuilder::new(name, channel)
+        .auto_upgrade(true)
+        .node_pool(
+            NodePoolBuilder::new("system".to_owned(), "e2-standard-4".to_owned())
+                .autoscaling(1, 3)
+                .taint(Taint::new(
+                    "CriticalAddonsOnly",
+                    "true",
+                    TaintEffect::NoSchedule,
+                )),
+        )
+        .node_pool(
+            NodePoolBuilder::new("compute".to_owned(), "c2-standard-16".to_owned())
+                .autoscaling(2, 20)
+                .label("workload", "batch")
+                .zone("europe-west1-b")
+                .zone("europe-west1-c"),
+        )
+        .node_pool(
+            NodePoolBuilder::new("spot".to_owned(), "c2-standard-16".to_owned())
+                .autoscaling(0, 50)
+                .spot(true)
+                .taint(Taint::new("spot", "true", TaintEffect::NoExecute)),
+        )
+        .build();
+    match &complete_cluster {
+        Ok(cluster) => {
+            writeln!(out, "Cluster {} ({})", cluster.name(), cluster.version())?;
+            for pool in cluster.node_pools() {
+                writeln!(
+                    out,
+                    "  pool {}: {} x {}..{}{}",
+                    pool.name(),
+                    pool.machine_type(),
+                    pool.min_nodes(),
+                    pool.max_nodes(),
+                    if pool.spot() { " (spot)" } else { "" }
+                )?;
+            }
+        }
+        Err(err) => writeln!(out, "{err}")?,
+    }
+
+    // Rolling the cluster forward to the next minor version without the spot pool
+    if let Ok(current) = &complete_cluster {
+        let desired = KubernetesClusterBuilder::new(current.name().to_owned(), "1.30".to_owned())
+            .auto_upgrade(true)
+            .node_pools(
+                current
+                    .node_pools()
+                    .iter()
+                    .filter(|pool| !pool.spot())
+                    .cloned()
+                    .collect(),
+            )
+            .build();
+        if let Ok(desired) = desired {
+            match upgrade::UpgradePlan::diff(current, &desired) {
+                Ok(plan) => {
+                    write!(out, "Upgrade plan:\n{plan}")?;
+                    let mut provisioner = upgrade::SimulatedProvisioner::new(current);
+                    match plan.apply(&mut provisioner) {
+                        Ok(()) => writeln!(
+                            out,
+                            "Applied, the cluster runs {}",
+                            provisioner.cluster().version()
+                        )?,
+                        Err(err) => writeln!(out, "{err}")?,
+                    }
+                }
+                Err(err) => writeln!(out, "{err}")?,
+            }
+        }
+    }
+
+    // Every violation is reported, not only the first one
+    let invalid_cluster = KubernetesClusterBuilder::new("My_Cluster".to_owned(), version)
+        .auto_upgrade(true)
+        .node_pool(NodePoolBuilder::new("pool".to_owned(), "e2-small".to_owned()).autoscaling(5, 2))
+        .node_pool(
+            NodePoolBuilder::new("pool".to_owned(), String::new())
+                .zone("europe-west1-b")
+                .zone("europe-west1-b"),
+        )
+        .build();
+    match invalid_cluster {
+        Ok(cluster) => writeln!(out, "{cluster:?}")?,
+        Err(err) => writeln!(out, "{err}")?,
+    }
+
+    // The environment beats the file, the explicit call beats both
+    let mut layered = layered::LayeredClusterBuilder::new();
+    layered
+        .file_contents("cluster.conf", "name = layered-cluster\nversion = 1.29\n")
+        .and_then(|layered| layered.env_vars([("DESIGN_PATTERNS_CLUSTER_VERSION", "1.30")]))
+        .map_err(io::Error::other)?
+        .auto_upgrade(true);
+    match layered.build() {
+        Ok(cluster) => writeln!(out, "{cluster:?}")?,
+        Err(err) => writeln!(out, "{err}")?,
+    }
+    for setting in layered.sources() {
+        writeln!(out, "  {setting}")?;
+    }
+
+    // Leaving out .name() or .version() here wouldn't compile
+    let typestate_cluster = typestate::ClusterBuilder::new()
+        .version("1.30".to_owned())
+        .auto_upgrade(true)
+        .name("typed-cluster".to_owned())
+        .build();
+    writeln!(out, "{typestate_cluster:?}")
+}
@@ -0,0 +1,348 @@
+// Layered configuration
+// LayeredClusterBuilder fills a KubernetesClusterBuilder from several sources. Each source is a
+// layer, and a value from a higher layer replaces the one from a lower layer, whatever the order
+// the sources are added in:
+//
+//     1. built-in defaults, version MAX_VERSION and auto_upgrade off
+//     2. config files, `key = value` lines, with `#` comments
+//     3. environment variables, DESIGN_PATTERNS_CLUSTER_NAME, _VERSION and _AUTO_UPGRADE
+//     4. explicit calls, name(), version() and auto_upgrade()
+//
+//     # cluster.conf
+//     name = prod
+//     version = 1.30
+//
+// Every final value remembers its layer and where exactly it came from, e.g. cluster.conf:3 or
+// DESIGN_PATTERNS_CLUSTER_VERSION. Node pools have no flat representation and are only added by
+// explicit calls.
+
+use std::error::Error;
+use std::ffi::OsStr;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::{
+    ClusterSpecError, KubernetesCluster, KubernetesClusterBuilder, NodePool, NodePoolBuilder,
+    MAX_VERSION,
+};
+
+pub const ENV_PREFIX: &str = "DESIGN_PATTERNS_CLUSTER_";
+
+// Lowest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Layer {
+    Default,
+    File,
+    Env,
+    Explicit,
+}
+
+impl fmt::Display for Layer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let layer = match self {
+            Layer::Default => "default",
+            Layer::File => "file",
+            Layer::Env => "env",
+            Layer::Explicit => "explicit",
+        };
+        f.write_str(layer)
+    }
+}
+
+// Keys in config files, and after ENV_PREFIX in upper case in the environment
+pub const FIELDS: [&str; 3] = ["name", "version", "auto_upgrade"];
+
+pub fn env_var(field: &str) -> String {
+    format!("{ENV_PREFIX}{}", field.to_ascii_uppercase())
+}
+
+fn field(key: &str) -> Option<&'static str> {
+    FIELDS.into_iter().find(|field| *field == key)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Setting {
+    pub field: &'static str,
+    pub value: String,
+    pub layer: Layer,
+    // File and line, environment variable, ...
+    pub origin: String,
+}
+
+impl fmt::Display for Setting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} = {} ({}, {})",
+            self.field, self.value, self.layer, self.origin
+        )
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    // A config file line that isn't `key = value`
+    Syntax {
+        origin: String,
+        message: String,
+    },
+    UnknownKey {
+        origin: String,
+        key: String,
+    },
+    InvalidValue {
+        field: &'static str,
+        origin: String,
+        value: String,
+    },
+    // An environment variable with the prefix whose name or value isn't UTF-8. origin is the
+    // name, lossily converted
+    NotUnicode {
+        origin: String,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io { path, source } => {
+                write!(f, "can't read {}: {source}", path.display())
+            }
+            ConfigError::Syntax { origin, message } => write!(f, "{origin}: {message}"),
+            ConfigError::UnknownKey { origin, key } => write!(f, "{origin}: unknown key `{key}`"),
+            ConfigError::InvalidValue {
+                field,
+                origin,
+                value,
+            } => write!(f, "{origin}: `{value}` is not a valid {field}"),
+            ConfigError::NotUnicode { origin } => write!(f, "{origin}: not valid Unicode"),
+        }
+    }
+}
+
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ConfigError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+pub struct LayeredClusterBuilder {
+    // In the order they were added
+    settings: Vec<Setting>,
+    node_pools: Vec<NodePool>,
+}
+
+impl LayeredClusterBuilder {
+    pub fn new() -> Self {
+        let mut builder = Self {
+            settings: Vec::new(),
+            node_pools: Vec::new(),
+        };
+        builder.set(
+            "version",
+            MAX_VERSION.to_string(),
+            Layer::Default,
+            "built-in",
+        );
+        builder.set(
+            "auto_upgrade",
+            "false".to_string(),
+            Layer::Default,
+            "built-in",
+        );
+        builder
+    }
+
+    fn set(&mut self, field: &'static str, value: String, layer: Layer, origin: &str) {
+        self.settings.push(Setting {
+            field,
+            value,
+            layer,
+            origin: origin.to_string(),
+        });
+    }
+
+    pub fn file(&mut self, path: impl AsRef<Path>) -> Result<&mut Self, ConfigError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|source| ConfigError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        self.file_contents(&path.display().to_string(), &contents)
+    }
+
+    // name is what errors and origins call the file. A file with an error adds no setting at all
+    pub fn file_contents(&mut self, name: &str, contents: &str) -> Result<&mut Self, ConfigError> {
+        let mut settings = Vec::new();
+        for (index, line) in contents.lines().enumerate() {
+            let origin = format!("{name}:{}", index + 1);
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(ConfigError::Syntax {
+                    origin,
+                    message: format!("expected `key = value`, got `{line}`"),
+                });
+            };
+            let key = key.trim();
+            let field = field(key).ok_or_else(|| ConfigError::UnknownKey {
+                origin: origin.clone(),
+                key: key.to_string(),
+            })?;
+            settings.push(parse_setting(field, value.trim(), Layer::File, origin)?);
+        }
+        self.settings.extend(settings);
+        Ok(self)
+    }
+
+    // Reads the DESIGN_PATTERNS_CLUSTER_ variables of the process
+    pub fn env(&mut self) -> Result<&mut Self, ConfigError> {
+        self.env_vars(std::env::vars_os())
+    }
+
+    // Variables without the prefix are ignored, whatever their encoding, unknown ones with it are an
+    // error. Like a file, the variables are only added when all of them are valid
+    pub fn env_vars<I, K, V>(&mut self, vars: I) -> Result<&mut Self, ConfigError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<OsStr>,
+        V: AsRef<OsStr>,
+    {
+        let mut settings = Vec::new();
+        for (key, value) in vars {
+            let key = key.as_ref();
+            if !key.as_encoded_bytes().starts_with(ENV_PREFIX.as_bytes()) {
+                continue;
+            }
+            let not_unicode = || ConfigError::NotUnicode {
+                origin: key.to_string_lossy().into_owned(),
+            };
+            let (key, value) = key
+                .to_str()
+                .zip(value.as_ref().to_str())
+                .ok_or_else(not_unicode)?;
+            let suffix = &key[ENV_PREFIX.len()..];
+            let field =
+                field(&suffix.to_ascii_lowercase()).ok_or_else(|| ConfigError::UnknownKey {
+                    origin: "environment".to_string(),
+                    key: key.to_string(),
+                })?;
+            settings.push(parse_setting(
+                field,
+                value.trim(),
+                Layer::Env,
+                key.to_string(),
+            )?);
+        }
+        self.settings.extend(settings);
+        Ok(self)
+    }
+
+    pub fn name(&mut self, name: String) -> &mut Self {
+        self.set("name", name, Layer::Explicit, "name()");
+        self
+    }
+
+    pub fn version(&mut self, version: String) -> &mut Self {
+        self.set("version", version, Layer::Explicit, "version()");
+        self
+    }
+
+    pub fn auto_upgrade(&mut self, auto_upgrade: bool) -> &mut Self {
+        self.set(
+            "auto_upgrade",
+            auto_upgrade.to_string(),
+            Layer::Explicit,
+            "auto_upgrade()",
+        );
+        self
+    }
+
+    pub fn node_pool(&mut self, pool: &mut NodePoolBuilder) -> &mut Self {
+        self.node_pools.push(pool.build());
+        self
+    }
+
+    // The setting that wins for the field: the highest layer, and within a layer the last one
+    pub fn source(&self, field: &str) -> Option<&Setting> {
+        self.settings
+            .iter()
+            .filter(|setting| setting.field == field)
+            .max_by_key(|setting| setting.layer)
+            .and_then(|winner| {
+                self.settings
+                    .iter()
+                    .rev()
+                    .find(|setting| setting.field == field && setting.layer == winner.layer)
+            })
+    }
+
+    // The final value of every field that has one
+    pub fn sources(&self) -> Vec<&Setting> {
+        FIELDS
+            .iter()
+            .filter_map(|field| self.source(field))
+            .collect()
+    }
+
+    // A missing name is reported by the validation of the builder
+    pub fn build(&self) -> Result<KubernetesCluster, ClusterSpecError> {
+        let value = |field| self.source(field).map(|setting| setting.value.clone());
+        KubernetesClusterBuilder::new(
+            value("name").unwrap_or_default(),
+            value("version").unwrap_or_default(),
+        )
+        .auto_upgrade(value("auto_upgrade").as_deref() == Some("true"))
+        .node_pools(self.node_pools.clone())
+        .build()
+    }
+}
+
+impl Default for LayeredClusterBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn parse_setting(
+    field: &'static str,
+    value: &str,
+    layer: Layer,
+    origin: String,
+) -> Result<Setting, ConfigError> {
+    let value = match field {
+        "auto_upgrade" => parse_bool(value)
+            .ok_or_else(|| ConfigError::InvalidValue {
+                field,
+                origin: origin.clone(),
+                value: value.to_string(),
+            })?
+            .to_string(),
+        _ => value.to_string(),
+    };
+    Ok(Setting {
+        field,
+        value,
+        layer,
+        origin,
+    })
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => Some(true),
+        "false" | "no" | "off" | "0" => Some(false),
+        _ => None,
+    }
+}
@@ -0,0 +1,517 @@
+// Cluster manifests
+// A KubernetesCluster is written as a declarative YAML or JSON manifest that can be kept in git and
+// reviewed as a file. Loading a manifest goes through KubernetesClusterBuilder, so a manifest is
+// validated exactly like a spec written in Rust, and a cluster round-trips without loss.
+//
+//     name: prod
+//     version: '1.30'
+//     auto_upgrade: true
+//     node_pools:
+//     - name: system
+//       machine_type: e2-standard-4
+//       min_nodes: 1
+//       max_nodes: 3
+//
+// The keys are the field names the builder reports violations with, and every error points to the
+// line of the field it is about: syntax errors, values of the wrong type, unknown fields and every
+// violation of the spec. The version has to be a quoted string, to YAML an unquoted 1.30 is the
+// number 1.3.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+use serde::de::{self, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use super::{
+    ClusterSpecError, KubernetesCluster, KubernetesClusterBuilder, NodePoolBuilder, Taint,
+    TaintEffect,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Yaml,
+    Json,
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Format::Yaml => f.write_str("YAML"),
+            Format::Json => f.write_str("JSON"),
+        }
+    }
+}
+
+// 1-based line and column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManifestError {
+    // The document is malformed, or a field is unknown or has a value of the wrong type
+    Parse {
+        format: Format,
+        // Path of the field, empty for the document itself
+        field: String,
+        location: Option<Location>,
+        message: String,
+    },
+    // A well-formed manifest of an invalid spec. locations has the place of every violation, in
+    // the same order, None for a field the manifest leaves out.
+    Invalid {
+        error: ClusterSpecError,
+        locations: Vec<Option<Location>>,
+    },
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ManifestError::Parse {
+                format,
+                field,
+                location,
+                message,
+            } => {
+                write!(f, "invalid {format} manifest")?;
+                if let Some(location) = location {
+                    write!(f, " at {location}")?;
+                }
+                if !field.is_empty() {
+                    write!(f, ", field {field}")?;
+                }
+                write!(f, ": {message}")
+            }
+            ManifestError::Invalid { error, locations } => {
+                write!(f, "invalid cluster spec")?;
+                for (violation, location) in error.violations().iter().zip(locations) {
+                    match location {
+                        Some(location) => write!(f, "\n  line {}: {violation}", location.line)?,
+                        None => write!(f, "\n  {violation}")?,
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for ManifestError {}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ClusterManifest {
+    name: String,
+    #[serde(deserialize_with = "quoted_version")]
+    version: String,
+    #[serde(default)]
+    auto_upgrade: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    node_pools: Vec<NodePoolManifest>,
+}
+
+// Only takes a string, a number is an error instead of being read back as text: 1.30 would
+// already have become 1.3
+fn quoted_version<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    deserializer.deserialize_any(QuotedVersion)
+}
+
+struct QuotedVersion;
+
+impl<'de> Visitor<'de> for QuotedVersion {
+    type Value = String;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a version in quotes, e.g. '1.30'")
+    }
+
+    fn visit_str<E>(self, version: &str) -> Result<String, E> {
+        Ok(version.to_string())
+    }
+}
+
+fn default_min_nodes() -> u32 {
+    1
+}
+
+fn default_max_nodes() -> u32 {
+    3
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+// Same defaults as NodePoolBuilder
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NodePoolManifest {
+    name: String,
+    machine_type: String,
+    #[serde(default = "default_min_nodes")]
+    min_nodes: u32,
+    #[serde(default = "default_max_nodes")]
+    max_nodes: u32,
+    #[serde(default, skip_serializing_if = "is_false")]
+    spot: bool,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    labels: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    taints: Vec<TaintManifest>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    zones: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TaintManifest {
+    key: String,
+    #[serde(default)]
+    value: String,
+    effect: TaintEffectManifest,
+}
+
+#[derive(Serialize, Deserialize)]
+enum TaintEffectManifest {
+    NoSchedule,
+    PreferNoSchedule,
+    NoExecute,
+}
+
+impl From<&KubernetesCluster> for ClusterManifest {
+    fn from(cluster: &KubernetesCluster) -> Self {
+        let node_pools = cluster
+            .node_pools()
+            .iter()
+            .map(|pool| NodePoolManifest {
+                name: pool.name().to_string(),
+                machine_type: pool.machine_type().to_string(),
+                min_nodes: pool.min_nodes(),
+                max_nodes: pool.max_nodes(),
+                spot: pool.spot(),
+                labels: pool.labels().clone(),
+                taints: pool
+                    .taints()
+                    .iter()
+                    .map(|taint| TaintManifest {
+                        key: taint.key.clone(),
+                        value: taint.value.clone(),
+                        effect: match taint.effect {
+                            TaintEffect::NoSchedule => TaintEffectManifest::NoSchedule,
+                            TaintEffect::PreferNoSchedule => TaintEffectManifest::PreferNoSchedule,
+                            TaintEffect::NoExecute => TaintEffectManifest::NoExecute,
+                        },
+                    })
+                    .collect(),
+                zones: pool.zones().to_vec(),
+            })
+            .collect();
+
+        Self {
+            name: cluster.name().to_string(),
+            version: cluster.version().to_string(),
+            auto_upgrade: cluster.auto_upgrade(),
+            node_pools,
+        }
+    }
+}
+
+impl ClusterManifest {
+    fn builder(self) -> KubernetesClusterBuilder {
+        let mut builder = KubernetesClusterBuilder::new(self.name, self.version);
+        builder.auto_upgrade(self.auto_upgrade);
+        for pool in self.node_pools {
+            let mut pool_builder = NodePoolBuilder::new(pool.name, pool.machine_type);
+            pool_builder
+                .autoscaling(pool.min_nodes, pool.max_nodes)
+                .spot(pool.spot)
+                .labels(pool.labels)
+                .zones(pool.zones);
+            for taint in pool.taints {
+                let effect = match taint.effect {
+                    TaintEffectManifest::NoSchedule => TaintEffect::NoSchedule,
+                    TaintEffectManifest::PreferNoSchedule => TaintEffect::PreferNoSchedule,
+                    TaintEffectManifest::NoExecute => TaintEffect::NoExecute,
+                };
+                pool_builder.taint(Taint::new(&taint.key, &taint.value, effect));
+            }
+            builder.node_pool(&mut pool_builder);
+        }
+        builder
+    }
+}
+
+pub fn to_yaml(cluster: &KubernetesCluster) -> String {
+    serde_yaml::to_string(&ClusterManifest::from(cluster)).expect("manifests have string keys")
+}
+
+pub fn to_json(cluster: &KubernetesCluster) -> String {
+    serde_json::to_string_pretty(&ClusterManifest::from(cluster))
+        .expect("manifests have string keys")
+        + "\n"
+}
+
+pub fn to_manifest(cluster: &KubernetesCluster, format: Format) -> String {
+    match format {
+        Format::Yaml => to_yaml(cluster),
+        Format::Json => to_json(cluster),
+    }
+}
+
+pub fn from_yaml(yaml: &str) -> Result<KubernetesCluster, ManifestError> {
+    from_manifest(yaml, Format::Yaml)
+}
+
+pub fn from_json(json: &str) -> Result<KubernetesCluster, ManifestError> {
+    from_manifest(json, Format::Json)
+}
+
+pub fn from_manifest(source: &str, format: Format) -> Result<KubernetesCluster, ManifestError> {
+    let manifest: ClusterManifest = parse(source, format)?;
+    manifest.builder().build().map_err(|error| {
+        let locations = error
+            .violations()
+            .iter()
+            .map(|violation| locate(source, format, violation.field()))
+            .collect();
+        ManifestError::Invalid { error, locations }
+    })
+}
+
+fn parse<T: for<'de> Deserialize<'de>>(source: &str, format: Format) -> Result<T, ManifestError> {
+    let parse_error = |field: String, location, message: String| ManifestError::Parse {
+        format,
+        field,
+        location,
+        message,
+    };
+    // "." is the path of the document itself
+    let field = |path: &serde_path_to_error::Path| {
+        let path = path.to_string();
+        if path == "." {
+            String::new()
+        } else {
+            path
+        }
+    };
+
+    match format {
+        Format::Yaml => {
+            let deserializer = serde_yaml::Deserializer::from_str(source);
+            serde_path_to_error::deserialize(deserializer).map_err(|err| {
+                let location = err.inner().location().map(|location| Location {
+                    line: location.line(),
+                    column: location.column(),
+                });
+                let field = field(err.path());
+                let message = yaml_message(err.inner());
+                // serde_yaml starts its messages with the path too
+                let message = match message.strip_prefix(&format!("{field}: ")) {
+                    Some(message) => message.to_string(),
+                    None => message,
+                };
+                parse_error(field, location, message)
+            })
+        }
+        Format::Json => {
+            let json_error = |field: String, err: &serde_json::Error| {
+                let location = Location {
+                    line: err.line(),
+                    column: err.column(),
+                };
+                parse_error(field, Some(location), json_message(err))
+            };
+            let mut deserializer = serde_json::Deserializer::from_str(source);
+            let value = serde_path_to_error::deserialize(&mut deserializer)
+                .map_err(|err| json_error(field(err.path()), err.inner()))?;
+            deserializer
+                .end()
+                .map_err(|err| json_error(String::new(), &err))?;
+            Ok(value)
+        }
+    }
+}
+
+// The errors of serde_yaml and serde_json end in their location, which ManifestError reports itself
+fn yaml_message(err: &serde_yaml::Error) -> String {
+    let message = err.to_string();
+    match err.location() {
+        Some(location) => strip_location(&message, location.line(), location.column()),
+        None => message,
+    }
+}
+
+fn json_message(err: &serde_json::Error) -> String {
+    strip_location(&err.to_string(), err.line(), err.column())
+}
+
+fn strip_location(message: &str, line: usize, column: usize) -> String {
+    let suffix = format!(" at line {line} column {column}");
+    message.strip_suffix(&suffix).unwrap_or(message).to_string()
+}
+
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+// node_pools[1].zones[0] as [Key(node_pools), Index(1), Key(zones), Index(0)]
+fn segments(path: &str) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    for part in path.split('.') {
+        let mut pieces = part.split('[');
+        if let Some(key) = pieces.next().filter(|key| !key.is_empty()) {
+            segments.push(Segment::Key(key.to_string()));
+        }
+        for index in pieces {
+            if let Ok(index) = index.trim_end_matches(']').parse() {
+                segments.push(Segment::Index(index));
+            }
+        }
+    }
+    segments
+}
+
+// Neither serde_yaml nor serde_json keep the position of a value. So the document is walked once
+// more down to the field, which then fails on purpose: the error carries the position the parser
+// was at. serde_json is still in front of the value then, while serde_yaml only records the
+// position of the value if the error happens while the value is being read.
+const FOUND: &str = "design-patterns: located";
+
+fn locate(source: &str, format: Format, field: &str) -> Option<Location> {
+    let segments = segments(field);
+    let seed = Locate {
+        segments: &segments,
+        read_value: format == Format::Yaml,
+    };
+    match format {
+        Format::Yaml => {
+            let err = seed
+                .deserialize(serde_yaml::Deserializer::from_str(source))
+                .err()?;
+            let location = err.location().filter(|_| err.to_string().contains(FOUND))?;
+            Some(Location {
+                line: location.line(),
+                column: location.column(),
+            })
+        }
+        Format::Json => {
+            let err = seed
+                .deserialize(&mut serde_json::Deserializer::from_str(source))
+                .err()?;
+            err.to_string().contains(FOUND).then(|| Location {
+                line: err.line(),
+                column: err.column(),
+            })
+        }
+    }
+}
+
+struct Locate<'a> {
+    segments: &'a [Segment],
+    // Fail while reading the value instead of before
+    read_value: bool,
+}
+
+impl<'a> Locate<'a> {
+    fn rest(&self, rest: &'a [Segment]) -> Self {
+        Self {
+            segments: rest,
+            read_value: self.read_value,
+        }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for Locate<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        match (self.segments.is_empty(), self.read_value) {
+            (true, true) => deserializer.deserialize_any(Found),
+            (true, false) => Err(de::Error::custom(FOUND)),
+            (false, _) => deserializer.deserialize_any(self),
+        }
+    }
+}
+
+// Fails on any value
+struct Found;
+
+impl<'de> Visitor<'de> for Found {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(FOUND)
+    }
+}
+
+impl<'de> Visitor<'de> for Locate<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a manifest")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let Some((Segment::Key(wanted), rest)) = self.segments.split_first() else {
+            while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+            return Ok(());
+        };
+        while let Some(key) = map.next_key::<String>()? {
+            if key == *wanted {
+                return map.next_value_seed(self.rest(rest));
+            }
+            map.next_value::<IgnoredAny>()?;
+        }
+        Ok(())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let Some((Segment::Index(wanted), rest)) = self.segments.split_first() else {
+            while seq.next_element::<IgnoredAny>()?.is_some() {}
+            return Ok(());
+        };
+        for _ in 0..*wanted {
+            if seq.next_element::<IgnoredAny>()?.is_none() {
+                return Ok(());
+            }
+        }
+        seq.next_element_seed(self.rest(rest)).map(|_| ())
+    }
+
+    // Anything else is a leaf, so the field isn't in the document
+    fn visit_bool<E>(self, _: bool) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_i64<E>(self, _: i64) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_u64<E>(self, _: u64) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_f64<E>(self, _: f64) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_str<E>(self, _: &str) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_unit<E>(self) -> Result<(), E> {
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+// Node pools
+// A cluster runs its workloads on node pools, groups of identical machines that autoscale between
+// a minimum and a maximum number of nodes. Each pool has its own nested builder, which is handed to
+// KubernetesClusterBuilder::node_pool(). The pools are validated with the rest of the cluster spec.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::Builder;
+
+// Upper bound of max_nodes for a single pool
+pub const MAX_POOL_NODES: u32 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaintEffect {
+    NoSchedule,
+    PreferNoSchedule,
+    NoExecute,
+}
+
+impl fmt::Display for TaintEffect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let effect = match self {
+            TaintEffect::NoSchedule => "NoSchedule",
+            TaintEffect::PreferNoSchedule => "PreferNoSchedule",
+            TaintEffect::NoExecute => "NoExecute",
+        };
+        f.write_str(effect)
+    }
+}
+
+// Keeps pods that don't tolerate it off the nodes of a pool
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Taint {
+    pub key: String,
+    pub value: String,
+    pub effect: TaintEffect,
+}
+
+impl Taint {
+    pub fn new(key: &str, value: &str, effect: TaintEffect) -> Self {
+        Self {
+            key: key.to_string(),
+            value: value.to_string(),
+            effect,
+        }
+    }
+}
+
+impl fmt::Display for Taint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}:{}", self.key, self.value, self.effect)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Builder)]
+pub struct NodePool {
+    name: String,
+    machine_type: String,
+    #[builder(default = 1)]
+    min_nodes: u32,
+    #[builder(default = 3)]
+    max_nodes: u32,
+    // Preemptible machines, cheaper but reclaimed at any time
+    #[builder(default)]
+    spot: bool,
+    #[builder(default)]
+    labels: BTreeMap<String, String>,
+    #[builder(default)]
+    taints: Vec<Taint>,
+    // Empty means every zone of the region
+    #[builder(default)]
+    zones: Vec<String>,
+}
+
+impl NodePool {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn machine_type(&self) -> &str {
+        &self.machine_type
+    }
+
+    pub fn min_nodes(&self) -> u32 {
+        self.min_nodes
+    }
+
+    pub fn max_nodes(&self) -> u32 {
+        self.max_nodes
+    }
+
+    pub fn spot(&self) -> bool {
+        self.spot
+    }
+
+    pub fn labels(&self) -> &BTreeMap<String, String> {
+        &self.labels
+    }
+
+    pub fn taints(&self) -> &[Taint] {
+        &self.taints
+    }
+
+    pub fn zones(&self) -> &[String] {
+        &self.zones
+    }
+
+    // For upgrade plans, which tell a resize from other changes of a pool
+    pub(super) fn set_autoscaling(&mut self, min_nodes: u32, max_nodes: u32) {
+        self.min_nodes = min_nodes;
+        self.max_nodes = max_nodes;
+    }
+}
+
+// Setters for one entry at a time, next to the derived ones for the whole collection
+impl NodePoolBuilder {
+    pub fn autoscaling(&mut self, min_nodes: u32, max_nodes: u32) -> &mut Self {
+        self.min_nodes(min_nodes).max_nodes(max_nodes)
+    }
+
+    pub fn label(&mut self, key: &str, value: &str) -> &mut Self {
+        self.labels
+            .get_or_insert_with(BTreeMap::new)
+            .insert(key.to_string(), value.to_string());
+        self
+    }
+
+    pub fn taint(&mut self, taint: Taint) -> &mut Self {
+        self.taints.get_or_insert_with(Vec::new).push(taint);
+        self
+    }
+
+    pub fn zone(&mut self, zone: &str) -> &mut Self {
+        self.zones
+            .get_or_insert_with(Vec::new)
+            .push(zone.to_string());
+        self
+    }
+}
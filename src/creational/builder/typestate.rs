@@ -0,0 +1,84 @@
+// Typestate builder
+// The type parameters of ClusterBuilder record whether the name and the version have been set.
+// name() and version() only exist while their field is missing and build() only once both are
+// set, so forgetting a required field or setting it twice is a compile error instead of a runtime
+// surprise. The values themselves are still validated by build().
+
+use super::{
+    ClusterSpecError, KubernetesCluster, KubernetesClusterBuilder, NodePool, NodePoolBuilder,
+};
+
+pub struct NoName;
+pub struct Name(String);
+pub struct NoVersion;
+pub struct Version(String);
+
+pub struct ClusterBuilder<N, V> {
+    name: N,
+    version: V,
+    auto_upgrade: Option<bool>,
+    node_pools: Vec<NodePool>,
+}
+
+impl ClusterBuilder<NoName, NoVersion> {
+    pub fn new() -> Self {
+        ClusterBuilder {
+            name: NoName,
+            version: NoVersion,
+            auto_upgrade: None,
+            node_pools: Vec::new(),
+        }
+    }
+}
+
+impl Default for ClusterBuilder<NoName, NoVersion> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> ClusterBuilder<NoName, V> {
+    pub fn name(self, name: String) -> ClusterBuilder<Name, V> {
+        ClusterBuilder {
+            name: Name(name),
+            version: self.version,
+            auto_upgrade: self.auto_upgrade,
+            node_pools: self.node_pools,
+        }
+    }
+}
+
+impl<N> ClusterBuilder<N, NoVersion> {
+    pub fn version(self, version: String) -> ClusterBuilder<N, Version> {
+        ClusterBuilder {
+            name: self.name,
+            version: Version(version),
+            auto_upgrade: self.auto_upgrade,
+            node_pools: self.node_pools,
+        }
+    }
+}
+
+// Optional fields can be set in any state
+impl<N, V> ClusterBuilder<N, V> {
+    pub fn auto_upgrade(mut self, auto_upgrade: bool) -> Self {
+        self.auto_upgrade = Some(auto_upgrade);
+        self
+    }
+
+    pub fn node_pool(mut self, pool: &mut NodePoolBuilder) -> Self {
+        self.node_pools.push(pool.build());
+        self
+    }
+}
+
+impl ClusterBuilder<Name, Version> {
+    pub fn build(self) -> Result<KubernetesCluster, ClusterSpecError> {
+        let mut builder = KubernetesClusterBuilder::new(self.name.0, self.version.0);
+        if let Some(auto_upgrade) = self.auto_upgrade {
+            builder.auto_upgrade(auto_upgrade);
+        }
+        builder.node_pools(self.node_pools);
+        builder.build()
+    }
+}
@@ -0,0 +1,579 @@
+// Upgrade plans
+// UpgradePlan::diff() compares the current and the desired spec of a cluster and orders the steps
+// that take one to the other. Nodes may never run a newer version than the control plane, and
+// workloads need somewhere to go before nodes are drained, so the order is always:
+//
+//     1. turn auto-upgrade off, so it doesn't race the rollout. When the version changes it is off
+//        during the whole rollout, even if both specs have it on
+//     2. upgrade the control plane
+//     3. add the new node pools, they come up at the new version
+//     4. upgrade the node pools that are kept, unless they are replaced anyway
+//     5. resize or replace the node pools whose spec changed
+//     6. remove the node pools that are gone
+//     7. turn auto-upgrade on
+//
+// Kubernetes upgrades one minor version at a time, so a plan that skips a minor version, changes
+// the major version or downgrades is rejected. Going from a release to the channel of its minor
+// version, e.g. 1.29.4 to 1.29, upgrades nothing: the cluster only follows the channel from then
+// on, in place of step 2 and without turning auto-upgrade off. A Provisioner applies the plan step by step. The
+// SimulatedProvisioner keeps the cluster in memory and can fail on purpose, which lets rollout
+// logic be tested without a real cluster. Like a real provider it rejects a step that would leave
+// an invalid spec behind, e.g. an unsupported version or a node pool whose max_nodes < min_nodes.
+
+use std::error::Error;
+use std::fmt;
+
+use super::{
+    ClusterSpecError, KubernetesCluster, KubernetesClusterBuilder, KubernetesVersion, NodePool,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Step {
+    SetAutoUpgrade(bool),
+    UpgradeControlPlane {
+        from: KubernetesVersion,
+        to: KubernetesVersion,
+    },
+    // The cluster follows the channel of the minor version it runs from now on
+    TrackChannel {
+        from: KubernetesVersion,
+        to: KubernetesVersion,
+    },
+    AddNodePool(NodePool),
+    // Drains and recreates the nodes of the pool at the new version
+    UpgradeNodePool {
+        pool: String,
+        from: KubernetesVersion,
+        to: KubernetesVersion,
+    },
+    ResizeNodePool {
+        pool: String,
+        min_nodes: u32,
+        max_nodes: u32,
+        // Lowering max_nodes can take nodes away
+        shrinks: bool,
+    },
+    // Anything but the autoscaling bounds changed, the pool is recreated
+    ReplaceNodePool {
+        from: NodePool,
+        to: NodePool,
+    },
+    RemoveNodePool(String),
+}
+
+impl Step {
+    // Whether the step evicts running workloads
+    pub fn is_disruptive(&self) -> bool {
+        match self {
+            Step::SetAutoUpgrade(_)
+            | Step::UpgradeControlPlane { .. }
+            | Step::TrackChannel { .. }
+            | Step::AddNodePool(_) => false,
+            Step::ResizeNodePool { shrinks, .. } => *shrinks,
+            Step::UpgradeNodePool { .. }
+            | Step::ReplaceNodePool { .. }
+            | Step::RemoveNodePool(_) => true,
+        }
+    }
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::SetAutoUpgrade(true) => write!(f, "turn auto-upgrade on"),
+            Step::SetAutoUpgrade(false) => write!(f, "turn auto-upgrade off"),
+            Step::UpgradeControlPlane { from, to } => {
+                write!(f, "upgrade control plane from {from} to {to}")
+            }
+            Step::TrackChannel { from, to } => {
+                write!(f, "follow the {to} channel instead of {from}")
+            }
+            Step::AddNodePool(pool) => write!(
+                f,
+                "add node pool {} ({} x {}..{})",
+                pool.name(),
+                pool.machine_type(),
+                pool.min_nodes(),
+                pool.max_nodes()
+            ),
+            Step::UpgradeNodePool { pool, from, to } => {
+                write!(f, "upgrade node pool {pool} from {from} to {to}")
+            }
+            Step::ResizeNodePool {
+                pool,
+                min_nodes,
+                max_nodes,
+                ..
+            } => write!(f, "resize node pool {pool} to {min_nodes}..{max_nodes}"),
+            Step::ReplaceNodePool { to, .. } => write!(f, "replace node pool {}", to.name()),
+            Step::RemoveNodePool(pool) => write!(f, "remove node pool {pool}"),
+        }?;
+        if self.is_disruptive() {
+            write!(f, " (disruptive)")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlanError {
+    // The specs are of two different clusters
+    ClusterMismatch {
+        current: String,
+        desired: String,
+    },
+    MajorUpgrade {
+        from: KubernetesVersion,
+        to: KubernetesVersion,
+    },
+    SkippedMinorVersions {
+        from: KubernetesVersion,
+        to: KubernetesVersion,
+    },
+    Downgrade {
+        from: KubernetesVersion,
+        to: KubernetesVersion,
+    },
+}
+
+impl fmt::Display for PlanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlanError::ClusterMismatch { current, desired } => {
+                write!(f, "`{desired}` is a different cluster than `{current}`")
+            }
+            PlanError::MajorUpgrade { from, to } => {
+                write!(
+                    f,
+                    "can't upgrade from {from} to {to}, the major version differs"
+                )
+            }
+            PlanError::SkippedMinorVersions { from, to } => write!(
+                f,
+                "can't upgrade from {from} to {to}, upgrade to {}.{} first",
+                from.major,
+                from.minor + 1
+            ),
+            PlanError::Downgrade { from, to } => {
+                write!(f, "can't downgrade from {from} to {to}")
+            }
+        }
+    }
+}
+
+impl Error for PlanError {}
+
+enum VersionChange {
+    None,
+    // From a release to the channel of the same minor version
+    Track {
+        from: KubernetesVersion,
+        to: KubernetesVersion,
+    },
+    Upgrade {
+        from: KubernetesVersion,
+        to: KubernetesVersion,
+    },
+}
+
+fn version_change(
+    from: KubernetesVersion,
+    to: KubernetesVersion,
+) -> Result<VersionChange, PlanError> {
+    if from == to {
+        return Ok(VersionChange::None);
+    }
+    if from.major != to.major {
+        return Err(PlanError::MajorUpgrade { from, to });
+    }
+    if to.minor < from.minor {
+        return Err(PlanError::Downgrade { from, to });
+    }
+    if to.minor > from.minor + 1 {
+        return Err(PlanError::SkippedMinorVersions { from, to });
+    }
+    // Within a minor version only a lower patch is a downgrade, a channel follows the latest one
+    if let (Some(from_patch), Some(to_patch)) = (from.patch, to.patch) {
+        if to.minor == from.minor && to_patch < from_patch {
+            return Err(PlanError::Downgrade { from, to });
+        }
+    }
+    if to.minor == from.minor && !from.is_channel() && to.is_channel() {
+        return Ok(VersionChange::Track { from, to });
+    }
+    Ok(VersionChange::Upgrade { from, to })
+}
+
+// Resizes or replaces a kept node pool whose spec changed
+fn pool_change(from: &NodePool, to: &NodePool) -> Option<Step> {
+    if from == to {
+        return None;
+    }
+    let mut resized = from.clone();
+    resized.set_autoscaling(to.min_nodes(), to.max_nodes());
+    if resized == *to {
+        Some(Step::ResizeNodePool {
+            pool: to.name().to_string(),
+            min_nodes: to.min_nodes(),
+            max_nodes: to.max_nodes(),
+            shrinks: to.max_nodes() < from.max_nodes(),
+        })
+    } else {
+        Some(Step::ReplaceNodePool {
+            from: from.clone(),
+            to: to.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct UpgradePlan {
+    steps: Vec<Step>,
+}
+
+impl UpgradePlan {
+    pub fn diff(
+        current: &KubernetesCluster,
+        desired: &KubernetesCluster,
+    ) -> Result<UpgradePlan, PlanError> {
+        if current.name() != desired.name() {
+            return Err(PlanError::ClusterMismatch {
+                current: current.name().to_string(),
+                desired: desired.name().to_string(),
+            });
+        }
+        let version = version_change(current.kubernetes_version(), desired.kubernetes_version())?;
+        let upgrade = match version {
+            VersionChange::Upgrade { from, to } => Some((from, to)),
+            VersionChange::None | VersionChange::Track { .. } => None,
+        };
+
+        let mut steps = Vec::new();
+        let pause_auto_upgrade =
+            current.auto_upgrade() && (upgrade.is_some() || !desired.auto_upgrade());
+        if pause_auto_upgrade {
+            steps.push(Step::SetAutoUpgrade(false));
+        }
+        match version {
+            VersionChange::None => {}
+            VersionChange::Track { from, to } => steps.push(Step::TrackChannel { from, to }),
+            VersionChange::Upgrade { from, to } => {
+                steps.push(Step::UpgradeControlPlane { from, to })
+            }
+        }
+
+        for pool in desired.node_pools() {
+            if current.node_pool(pool.name()).is_none() {
+                steps.push(Step::AddNodePool(pool.clone()));
+            }
+        }
+
+        let changes: Vec<(&NodePool, Option<Step>)> = current
+            .node_pools()
+            .iter()
+            .filter_map(|pool| Some((pool, pool_change(pool, desired.node_pool(pool.name())?))))
+            .collect();
+        if let Some((from, to)) = upgrade {
+            for (pool, change) in &changes {
+                // A replaced pool comes up at the new version anyway
+                if !matches!(change, Some(Step::ReplaceNodePool { .. })) {
+                    steps.push(Step::UpgradeNodePool {
+                        pool: pool.name().to_string(),
+                        from,
+                        to,
+                    });
+                }
+            }
+        }
+        steps.extend(changes.into_iter().filter_map(|(_, change)| change));
+
+        for pool in current.node_pools() {
+            if desired.node_pool(pool.name()).is_none() {
+                steps.push(Step::RemoveNodePool(pool.name().to_string()));
+            }
+        }
+        if desired.auto_upgrade() && (pause_auto_upgrade || !current.auto_upgrade()) {
+            steps.push(Step::SetAutoUpgrade(true));
+        }
+
+        Ok(UpgradePlan { steps })
+    }
+
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    pub fn is_disruptive(&self) -> bool {
+        self.steps.iter().any(Step::is_disruptive)
+    }
+
+    // Stops at the first step that fails
+    pub fn apply(&self, provisioner: &mut dyn Provisioner) -> Result<(), RolloutError> {
+        self.resume(provisioner, 0)
+    }
+
+    // Applies the steps from index applied on, e.g. RolloutError::applied after a failure. The
+    // spec doesn't record node pool versions, so diffing again after a failed rollout would miss
+    // pools that weren't upgraded yet.
+    pub fn resume(
+        &self,
+        provisioner: &mut dyn Provisioner,
+        applied: usize,
+    ) -> Result<(), RolloutError> {
+        for (index, step) in self.steps.iter().enumerate().skip(applied) {
+            provisioner.apply(step).map_err(|error| RolloutError {
+                applied: index,
+                step: Box::new(step.clone()),
+                error,
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for UpgradePlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.steps.is_empty() {
+            return writeln!(f, "nothing to do");
+        }
+        for (index, step) in self.steps.iter().enumerate() {
+            writeln!(f, "{}. {step}", index + 1)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProvisionError {
+    // The cluster isn't in the state the step expects
+    VersionMismatch {
+        expected: KubernetesVersion,
+        actual: KubernetesVersion,
+    },
+    UnknownPool(String),
+    PoolExists(String),
+    // A node pool would run a newer minor version than the control plane
+    VersionSkew {
+        pool: String,
+        version: KubernetesVersion,
+        control_plane: KubernetesVersion,
+    },
+    // The cluster would no longer have a valid spec after the step
+    InvalidSpec(ClusterSpecError),
+    // Injected by SimulatedProvisioner::fail_when
+    Simulated(String),
+}
+
+impl fmt::Display for ProvisionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProvisionError::VersionMismatch { expected, actual } => {
+                write!(f, "expected version {expected}, the cluster runs {actual}")
+            }
+            ProvisionError::UnknownPool(pool) => write!(f, "there is no node pool {pool}"),
+            ProvisionError::PoolExists(pool) => write!(f, "node pool {pool} exists already"),
+            ProvisionError::VersionSkew {
+                pool,
+                version,
+                control_plane,
+            } => write!(
+                f,
+                "node pool {pool} can't run {version}, the control plane runs {control_plane}"
+            ),
+            ProvisionError::InvalidSpec(error) => write!(f, "the step leads to an {error}"),
+            ProvisionError::Simulated(reason) => write!(f, "simulated failure: {reason}"),
+        }
+    }
+}
+
+impl Error for ProvisionError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RolloutError {
+    // Number of steps that were applied before the failure
+    pub applied: usize,
+    pub step: Box<Step>,
+    pub error: ProvisionError,
+}
+
+impl fmt::Display for RolloutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "step {} ({}) failed: {}",
+            self.applied + 1,
+            self.step,
+            self.error
+        )
+    }
+}
+
+impl Error for RolloutError {}
+
+pub trait Provisioner {
+    fn apply(&mut self, step: &Step) -> Result<(), ProvisionError>;
+}
+
+type FailurePredicate = Box<dyn Fn(&Step) -> bool>;
+
+// A cluster in memory. Every step checks that the cluster is in the state it expects, like a real
+// provider would, and is recorded in the log.
+pub struct SimulatedProvisioner {
+    name: String,
+    control_plane: KubernetesVersion,
+    auto_upgrade: bool,
+    pools: Vec<(NodePool, KubernetesVersion)>,
+    log: Vec<String>,
+    fail_when: Option<FailurePredicate>,
+}
+
+impl SimulatedProvisioner {
+    pub fn new(cluster: &KubernetesCluster) -> Self {
+        let version = cluster.kubernetes_version();
+        Self {
+            name: cluster.name().to_string(),
+            control_plane: version,
+            auto_upgrade: cluster.auto_upgrade(),
+            pools: cluster
+                .node_pools()
+                .iter()
+                .map(|pool| (pool.clone(), version))
+                .collect(),
+            log: Vec::new(),
+            fail_when: None,
+        }
+    }
+
+    // Makes every step that matches fail, e.g. to test what a half applied rollout leaves behind
+    pub fn fail_when(&mut self, predicate: impl Fn(&Step) -> bool + 'static) -> &mut Self {
+        self.fail_when = Some(Box::new(predicate));
+        self
+    }
+
+    pub fn stop_failing(&mut self) -> &mut Self {
+        self.fail_when = None;
+        self
+    }
+
+    // Applied steps, in order
+    pub fn log(&self) -> &[String] {
+        &self.log
+    }
+
+    pub fn control_plane_version(&self) -> KubernetesVersion {
+        self.control_plane
+    }
+
+    pub fn pool_version(&self, pool: &str) -> Option<KubernetesVersion> {
+        self.pool(pool).map(|index| self.pools[index].1)
+    }
+
+    // The spec the simulated cluster is in now
+    pub fn cluster(&self) -> KubernetesCluster {
+        self.spec()
+            .expect("apply() rejects the steps that would make the spec invalid")
+    }
+
+    fn spec(&self) -> Result<KubernetesCluster, ClusterSpecError> {
+        KubernetesClusterBuilder::new(self.name.clone(), self.control_plane.to_string())
+            .auto_upgrade(self.auto_upgrade)
+            .node_pools(self.pools.iter().map(|(pool, _)| pool.clone()).collect())
+            .build()
+    }
+
+    fn pool(&self, name: &str) -> Option<usize> {
+        self.pools.iter().position(|(pool, _)| pool.name() == name)
+    }
+
+    fn existing_pool(&self, name: &str) -> Result<usize, ProvisionError> {
+        self.pool(name)
+            .ok_or_else(|| ProvisionError::UnknownPool(name.to_string()))
+    }
+
+    fn expect_version(
+        expected: KubernetesVersion,
+        actual: KubernetesVersion,
+    ) -> Result<(), ProvisionError> {
+        if expected == actual {
+            Ok(())
+        } else {
+            Err(ProvisionError::VersionMismatch { expected, actual })
+        }
+    }
+}
+
+impl Provisioner for SimulatedProvisioner {
+    fn apply(&mut self, step: &Step) -> Result<(), ProvisionError> {
+        if self.fail_when.as_ref().is_some_and(|fail| fail(step)) {
+            return Err(ProvisionError::Simulated(step.to_string()));
+        }
+
+        // Put back when the step turns out to leave an invalid spec
+        let before = (self.control_plane, self.auto_upgrade, self.pools.clone());
+        match step {
+            Step::SetAutoUpgrade(auto_upgrade) => self.auto_upgrade = *auto_upgrade,
+            Step::UpgradeControlPlane { from, to } => {
+                Self::expect_version(*from, self.control_plane)?;
+                self.control_plane = *to;
+            }
+            Step::TrackChannel { from, to } => {
+                Self::expect_version(*from, self.control_plane)?;
+                self.control_plane = *to;
+                // The nodes keep running the same release, which is now part of the channel
+                for (_, version) in &mut self.pools {
+                    if version == from {
+                        *version = *to;
+                    }
+                }
+            }
+            Step::AddNodePool(pool) => {
+                if self.pool(pool.name()).is_some() {
+                    return Err(ProvisionError::PoolExists(pool.name().to_string()));
+                }
+                self.pools.push((pool.clone(), self.control_plane));
+            }
+            Step::UpgradeNodePool { pool, from, to } => {
+                let index = self.existing_pool(pool)?;
+                Self::expect_version(*from, self.pools[index].1)?;
+                let control_plane = (self.control_plane.major, self.control_plane.minor);
+                if (to.major, to.minor) > control_plane {
+                    return Err(ProvisionError::VersionSkew {
+                        pool: pool.clone(),
+                        version: *to,
+                        control_plane: self.control_plane,
+                    });
+                }
+                self.pools[index].1 = *to;
+            }
+            Step::ResizeNodePool {
+                pool,
+                min_nodes,
+                max_nodes,
+                ..
+            } => {
+                let index = self.existing_pool(pool)?;
+                self.pools[index].0.set_autoscaling(*min_nodes, *max_nodes);
+            }
+            Step::ReplaceNodePool { from, to } => {
+                let index = self.existing_pool(from.name())?;
+                self.pools[index].0 = to.clone();
+                self.pools[index].1 = self.control_plane;
+            }
+            Step::RemoveNodePool(pool) => {
+                let index = self.existing_pool(pool)?;
+                self.pools.remove(index);
+            }
+        }
+        if let Err(error) = self.spec() {
+            (self.control_plane, self.auto_upgrade, self.pools) = before;
+            return Err(ProvisionError::InvalidSpec(error));
+        }
+
+        self.log.push(step.to_string());
+        Ok(())
+    }
+}
@@ -0,0 +1,302 @@
+// Creational Pattern - Dependency Injection
+// Instead of every object wiring its own dependencies by hand (like Operation building its
+// PlaceOrder, Payment and Delivery), a container knows how to provide every service and resolves
+// a whole dependency graph on request. Each service is registered with a lifetime:
+//   Singleton - created once and shared by the whole container
+//   Transient - created every time it is resolved
+//   Scoped    - created once per Scope, e.g. per request
+// Services are keyed by type, so trait objects (Rc<dyn Trait>) can be registered as well.
+
+use std::any::{type_name, Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+use std::rc::Rc;
+
+use crate::structural::facade::{Delivery, Operation, Payment, PlaceOrder};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lifetime {
+    Singleton,
+    Transient,
+    Scoped,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolveError {
+    // No provider is registered for `service`
+    Missing {
+        service: &'static str,
+        path: Vec<&'static str>,
+    },
+    // `path` starts and ends with the same service
+    Cycle {
+        path: Vec<&'static str>,
+    },
+    // A scoped service was resolved outside of a Scope, or by a singleton
+    ScopeRequired {
+        service: &'static str,
+        path: Vec<&'static str>,
+    },
+}
+
+impl ResolveError {
+    // Services being resolved when the error happened, outermost first
+    pub fn path(&self) -> &[&'static str] {
+        match self {
+            ResolveError::Missing { path, .. } => path,
+            ResolveError::Cycle { path } => path,
+            ResolveError::ScopeRequired { path, .. } => path,
+        }
+    }
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResolveError::Missing { service, path } => {
+                write!(f, "no provider registered for `{service}`")?;
+                if !path.is_empty() {
+                    write!(f, " (required by {})", path.join(" -> "))?;
+                }
+                Ok(())
+            }
+            ResolveError::Cycle { path } => {
+                write!(f, "dependency cycle: {}", path.join(" -> "))
+            }
+            ResolveError::ScopeRequired { service, path } => {
+                write!(
+                    f,
+                    "scoped service `{service}` can only be resolved in a scope"
+                )?;
+                if !path.is_empty() {
+                    write!(f, " (required by {})", path.join(" -> "))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for ResolveError {}
+
+type Provider<T> = Box<dyn Fn(&Resolver<'_>) -> Result<Rc<T>, ResolveError>>;
+
+struct Registration {
+    lifetime: Lifetime,
+    // Provider<T> of the registered type
+    provider: Box<dyn Any>,
+}
+
+// Instances already created, each one an Rc<T> keyed by the TypeId of T
+type Instances = RefCell<HashMap<TypeId, Box<dyn Any>>>;
+
+#[derive(Default)]
+pub struct Container {
+    registrations: HashMap<TypeId, Registration>,
+    singletons: Instances,
+}
+
+impl Container {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Registering a type twice replaces the previous provider
+    pub fn register<T, F>(&mut self, lifetime: Lifetime, provider: F) -> &mut Self
+    where
+        T: ?Sized + 'static,
+        F: Fn(&Resolver<'_>) -> Result<Rc<T>, ResolveError> + 'static,
+    {
+        let provider: Provider<T> = Box::new(provider);
+        self.registrations.insert(
+            TypeId::of::<T>(),
+            Registration {
+                lifetime,
+                provider: Box::new(provider),
+            },
+        );
+        self.singletons.get_mut().remove(&TypeId::of::<T>());
+        self
+    }
+
+    pub fn singleton<T, F>(&mut self, provider: F) -> &mut Self
+    where
+        T: ?Sized + 'static,
+        F: Fn(&Resolver<'_>) -> Result<Rc<T>, ResolveError> + 'static,
+    {
+        self.register(Lifetime::Singleton, provider)
+    }
+
+    pub fn transient<T, F>(&mut self, provider: F) -> &mut Self
+    where
+        T: ?Sized + 'static,
+        F: Fn(&Resolver<'_>) -> Result<Rc<T>, ResolveError> + 'static,
+    {
+        self.register(Lifetime::Transient, provider)
+    }
+
+    pub fn scoped<T, F>(&mut self, provider: F) -> &mut Self
+    where
+        T: ?Sized + 'static,
+        F: Fn(&Resolver<'_>) -> Result<Rc<T>, ResolveError> + 'static,
+    {
+        self.register(Lifetime::Scoped, provider)
+    }
+
+    pub fn is_registered<T: ?Sized + 'static>(&self) -> bool {
+        self.registrations.contains_key(&TypeId::of::<T>())
+    }
+
+    // Resolves outside of any scope, so scoped services (and anything depending on them) fail
+    pub fn resolve<T: ?Sized + 'static>(&self) -> Result<Rc<T>, ResolveError> {
+        Resolver::root(self, None).resolve()
+    }
+
+    pub fn create_scope(&self) -> Scope<'_> {
+        Scope {
+            container: self,
+            instances: RefCell::default(),
+        }
+    }
+}
+
+pub struct Scope<'a> {
+    container: &'a Container,
+    instances: Instances,
+}
+
+impl Scope<'_> {
+    pub fn resolve<T: ?Sized + 'static>(&self) -> Result<Rc<T>, ResolveError> {
+        Resolver::root(self.container, Some(self)).resolve()
+    }
+}
+
+// Handed to providers to resolve their own dependencies. It remembers which services are being
+// resolved, which is how cycles are detected and how errors report their path.
+pub struct Resolver<'a> {
+    container: &'a Container,
+    scope: Option<&'a Scope<'a>>,
+    path: Vec<(TypeId, &'static str)>,
+}
+
+impl<'a> Resolver<'a> {
+    fn root(container: &'a Container, scope: Option<&'a Scope<'a>>) -> Self {
+        Self {
+            container,
+            scope,
+            path: Vec::new(),
+        }
+    }
+
+    fn path_names(&self) -> Vec<&'static str> {
+        self.path.iter().map(|(_, name)| *name).collect()
+    }
+
+    pub fn resolve<T: ?Sized + 'static>(&self) -> Result<Rc<T>, ResolveError> {
+        let id = TypeId::of::<T>();
+        let service = type_name::<T>();
+
+        if self.path.iter().any(|(visited, _)| *visited == id) {
+            let mut path = self.path_names();
+            path.push(service);
+            return Err(ResolveError::Cycle { path });
+        }
+
+        let registration =
+            self.container
+                .registrations
+                .get(&id)
+                .ok_or_else(|| ResolveError::Missing {
+                    service,
+                    path: self.path_names(),
+                })?;
+        let provider = registration
+            .provider
+            .downcast_ref::<Provider<T>>()
+            .expect("provider is registered under the TypeId of its service");
+
+        let instances = match registration.lifetime {
+            Lifetime::Singleton => Some(&self.container.singletons),
+            Lifetime::Scoped => match self.scope {
+                Some(scope) => Some(&scope.instances),
+                None => {
+                    return Err(ResolveError::ScopeRequired {
+                        service,
+                        path: self.path_names(),
+                    })
+                }
+            },
+            Lifetime::Transient => None,
+        };
+
+        if let Some(instance) = instances.and_then(|instances| cached::<T>(instances, id)) {
+            return Ok(instance);
+        }
+
+        let mut path = self.path.clone();
+        path.push((id, service));
+        let resolver = Resolver {
+            container: self.container,
+            // A singleton outlives every scope, so it must not capture a scoped service
+            scope: match registration.lifetime {
+                Lifetime::Singleton => None,
+                _ => self.scope,
+            },
+            path,
+        };
+        let instance = provider(&resolver)?;
+
+        if let Some(instances) = instances {
+            instances
+                .borrow_mut()
+                .insert(id, Box::new(Rc::clone(&instance)));
+        }
+        Ok(instance)
+    }
+}
+
+fn cached<T: ?Sized + 'static>(instances: &Instances, id: TypeId) -> Option<Rc<T>> {
+    instances
+        .borrow()
+        .get(&id)
+        .and_then(|instance| instance.downcast_ref::<Rc<T>>())
+        .cloned()
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let mut container = Container::new();
+    container
+        .singleton(|_| Ok(Rc::new(PlaceOrder)))
+        .singleton(|_| Ok(Rc::new(Payment)))
+        .singleton(|_| Ok(Rc::new(Delivery)))
+        .transient(|resolver| {
+            Ok(Rc::new(Operation {
+                order: *resolver.resolve::<PlaceOrder>()?,
+                payment: *resolver.resolve::<Payment>()?,
+                delivery: *resolver.resolve::<Delivery>()?,
+            }))
+        });
+
+    // The Facade no longer builds its subsystems, the container wires them
+    match container.resolve::<Operation>() {
+        Ok(operation) => operation.complete_order(out)?,
+        Err(err) => writeln!(out, "{err}")?,
+    }
+
+    // Without a Payment provider the error names the whole path
+    let mut broken = Container::new();
+    broken.transient(|resolver| {
+        Ok(Rc::new(Operation {
+            order: PlaceOrder,
+            payment: *resolver.resolve::<Payment>()?,
+            delivery: Delivery,
+        }))
+    });
+    if let Err(err) = broken.resolve::<Operation>() {
+        writeln!(out, "{err}")?;
+    }
+    Ok(())
+}
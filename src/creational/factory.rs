@@ -0,0 +1,185 @@
+// Creational Pattern - Factory
+// The Factory design pattern allows us to encapsulate the object creation logic and provide a
+// unified interface (create_animal method) for creating different types of objects. This provides
+// flexibility and allows for easier maintenance and future extensibility.
+
+#[cfg(feature = "plugins")]
+pub mod plugin;
+#[cfg(feature = "serialization")]
+pub mod serialization;
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+
+pub trait Animal {
+    fn speak(&self, out: &mut dyn Write) -> io::Result<()>;
+
+    // Name the animal is registered under in AnimalFactory. The default keeps animals written before
+    // kind() existed compiling, but their type name is unlikely to be registered, so override it
+    fn kind(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+}
+
+pub struct Dog;
+pub struct Cat;
+
+impl Animal for Dog {
+    fn speak(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Dog says: Woof!")
+    }
+
+    fn kind(&self) -> &str {
+        AnimalType::Dog.name()
+    }
+}
+
+impl Animal for Cat {
+    fn speak(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Cat says: Meow!")
+    }
+
+    fn kind(&self) -> &str {
+        AnimalType::Cat.name()
+    }
+}
+
+pub enum AnimalType {
+    Dog,
+    Cat,
+}
+
+impl AnimalType {
+    // Name under which the animal is registered in AnimalFactory::new()
+    pub fn name(&self) -> &'static str {
+        match self {
+            AnimalType::Dog => "dog",
+            AnimalType::Cat => "cat",
+        }
+    }
+}
+
+pub type AnimalConstructor = Box<dyn Fn() -> Box<dyn Animal> + Send + Sync>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnimalFactoryError {
+    UnknownAnimal(String),
+}
+
+impl fmt::Display for AnimalFactoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnimalFactoryError::UnknownAnimal(name) => {
+                write!(f, "no animal is registered under `{name}`")
+            }
+        }
+    }
+}
+
+impl Error for AnimalFactoryError {}
+
+// The closed create_animal() needs an edit for every new animal. An AnimalFactory instance instead
+// keeps a registry of constructors keyed by name, so other crates can register their own animals
+// at runtime and create them by name, e.g. from a config file.
+pub struct AnimalFactory {
+    constructors: BTreeMap<String, AnimalConstructor>,
+}
+
+// Factory Method
+impl AnimalFactory {
+    pub fn create_animal(animal_type: AnimalType) -> Box<dyn Animal> {
+        match animal_type {
+            AnimalType::Dog => Box::new(Dog),
+            AnimalType::Cat => Box::new(Cat),
+        }
+    }
+
+    // Registry with the built-in animals ("dog" and "cat")
+    pub fn new() -> Self {
+        let mut factory = Self::empty();
+        factory
+            .register(AnimalType::Dog.name(), || Box::new(Dog))
+            .register(AnimalType::Cat.name(), || Box::new(Cat));
+        factory
+    }
+
+    pub fn empty() -> Self {
+        Self {
+            constructors: BTreeMap::new(),
+        }
+    }
+
+    // Registering a name twice replaces the previous constructor
+    pub fn register<F>(&mut self, name: impl Into<String>, constructor: F) -> &mut Self
+    where
+        F: Fn() -> Box<dyn Animal> + Send + Sync + 'static,
+    {
+        self.constructors.insert(name.into(), Box::new(constructor));
+        self
+    }
+
+    pub fn unregister(&mut self, name: &str) -> Option<AnimalConstructor> {
+        self.constructors.remove(name)
+    }
+
+    pub fn is_registered(&self, name: &str) -> bool {
+        self.constructors.contains_key(name)
+    }
+
+    // Registered names in alphabetical order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.constructors.keys().map(String::as_str)
+    }
+
+    pub fn create(&self, name: &str) -> Result<Box<dyn Animal>, AnimalFactoryError> {
+        self.constructors
+            .get(name)
+            .map(|constructor| constructor())
+            .ok_or_else(|| AnimalFactoryError::UnknownAnimal(name.to_string()))
+    }
+}
+
+impl Default for AnimalFactory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let dog = AnimalFactory::create_animal(AnimalType::Dog);
+    dog.speak(out)?;
+
+    let cat = AnimalFactory::create_animal(AnimalType::Cat);
+    cat.speak(out)?;
+
+    // Registry based factory, extended at runtime with an animal it didn't know about
+    struct Cow;
+
+    impl Animal for Cow {
+        fn speak(&self, out: &mut dyn Write) -> io::Result<()> {
+            writeln!(out, "Cow says: Moo!")
+        }
+
+        fn kind(&self) -> &str {
+            "cow"
+        }
+    }
+
+    let mut factory = AnimalFactory::new();
+    factory.register("cow", || Box::new(Cow));
+    writeln!(
+        out,
+        "Registered animals: {}",
+        factory.names().collect::<Vec<_>>().join(", ")
+    )?;
+
+    for name in ["dog", "cow", "lion"] {
+        match factory.create(name) {
+            Ok(animal) => animal.speak(out)?,
+            Err(err) => writeln!(out, "{err}")?,
+        }
+    }
+    Ok(())
+}
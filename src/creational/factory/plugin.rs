@@ -0,0 +1,237 @@
+// Animal plugins
+// A plugin is a cdylib that depends on this crate and exports its registration function with
+// export_animal_plugin!(). Loading it runs that function against an AnimalFactory, after which
+// the plugin's animals are created by name like any built-in one.
+//
+// Rust has no stable ABI, so a plugin must be built with the same compiler as the host. On top of
+// that the declaration carries PLUGIN_ABI_VERSION and the version of this crate the plugin was
+// built against, and both have to match before the registration function is ever called.
+//
+//     fn register(factory: &mut AnimalFactory) {
+//         factory.register("cow", || Box::new(Cow));
+//     }
+//
+//     design_patterns::export_animal_plugin!(register);
+
+use std::error::Error;
+use std::ffi::{c_char, CStr};
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use libloading::Library;
+
+use super::AnimalFactory;
+
+// Bumped whenever PluginDeclaration, the registration contract or the Animal trait changes, a new
+// trait method changes the vtable of every Box<dyn Animal> a plugin hands out
+//   2: Animal::kind()
+pub const PLUGIN_ABI_VERSION: u32 = 2;
+
+// Version of this crate, NUL terminated so it can be embedded in a PluginDeclaration
+pub const CORE_VERSION: &CStr =
+    match CStr::from_bytes_with_nul(concat!(env!("CARGO_PKG_VERSION"), "\0").as_bytes()) {
+        Ok(version) => version,
+        Err(_) => panic!("crate version contains a NUL byte"),
+    };
+
+// Name of the static every plugin exports, see export_animal_plugin!()
+pub const PLUGIN_DECLARATION_SYMBOL: &str = "DESIGN_PATTERNS_ANIMAL_PLUGIN";
+
+// abi_version must stay the first field, the loader reads it before trusting anything else
+#[repr(C)]
+pub struct PluginDeclaration {
+    pub abi_version: u32,
+    pub core_version: *const c_char,
+    pub register: fn(&mut AnimalFactory),
+}
+
+// core_version only ever points at a 'static string
+unsafe impl Sync for PluginDeclaration {}
+
+#[macro_export]
+macro_rules! export_animal_plugin {
+    ($register:path) => {
+        #[no_mangle]
+        pub static DESIGN_PATTERNS_ANIMAL_PLUGIN:
+            $crate::creational::factory::plugin::PluginDeclaration =
+            $crate::creational::factory::plugin::PluginDeclaration {
+                abi_version: $crate::creational::factory::plugin::PLUGIN_ABI_VERSION,
+                core_version: $crate::creational::factory::plugin::CORE_VERSION.as_ptr(),
+                register: $register,
+            };
+    };
+}
+
+#[derive(Debug)]
+pub enum PluginError {
+    ReadDir {
+        dir: PathBuf,
+        source: io::Error,
+    },
+    Load {
+        path: PathBuf,
+        source: libloading::Error,
+    },
+    MissingDeclaration {
+        path: PathBuf,
+    },
+    IncompatibleAbi {
+        path: PathBuf,
+        expected: u32,
+        found: u32,
+    },
+    IncompatibleVersion {
+        path: PathBuf,
+        expected: String,
+        found: String,
+    },
+}
+
+impl fmt::Display for PluginError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PluginError::ReadDir { dir, .. } => {
+                write!(f, "failed to read plugin directory {}", dir.display())
+            }
+            PluginError::Load { path, .. } => {
+                write!(f, "failed to load plugin {}", path.display())
+            }
+            PluginError::MissingDeclaration { path } => write!(
+                f,
+                "{} is not an animal plugin: it doesn't export `{PLUGIN_DECLARATION_SYMBOL}`",
+                path.display()
+            ),
+            PluginError::IncompatibleAbi {
+                path,
+                expected,
+                found,
+            } => write!(
+                f,
+                "plugin {} uses plugin ABI version {found}, expected {expected}",
+                path.display()
+            ),
+            PluginError::IncompatibleVersion {
+                path,
+                expected,
+                found,
+            } => write!(
+                f,
+                "plugin {} was built against design-patterns {found}, expected {expected}",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl Error for PluginError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PluginError::ReadDir { source, .. } => Some(source),
+            PluginError::Load { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadedPlugin {
+    pub path: PathBuf,
+    // Names the plugin registered (or replaced) in the factory
+    pub animals: Vec<String>,
+}
+
+/// Loads the plugin at `path` and registers its animals in `factory`.
+///
+/// A plugin that loaded successfully is never unloaded, since the constructors and the animals
+/// they create point into its code.
+///
+/// # Safety
+///
+/// Loading a library runs its initialisation code, and the plugin is trusted to be a cdylib built
+/// with export_animal_plugin!() by the same compiler as this crate.
+pub unsafe fn load_plugin(
+    factory: &mut AnimalFactory,
+    path: &Path,
+) -> Result<LoadedPlugin, PluginError> {
+    let library = Library::new(path).map_err(|source| PluginError::Load {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    let declaration = library
+        .get::<*const PluginDeclaration>(PLUGIN_DECLARATION_SYMBOL.as_bytes())
+        .map(|symbol| *symbol)
+        .map_err(|_| PluginError::MissingDeclaration {
+            path: path.to_path_buf(),
+        })?;
+
+    let abi_version = (*declaration).abi_version;
+    if abi_version != PLUGIN_ABI_VERSION {
+        return Err(PluginError::IncompatibleAbi {
+            path: path.to_path_buf(),
+            expected: PLUGIN_ABI_VERSION,
+            found: abi_version,
+        });
+    }
+
+    let core_version = CStr::from_ptr((*declaration).core_version);
+    if core_version != CORE_VERSION {
+        return Err(PluginError::IncompatibleVersion {
+            path: path.to_path_buf(),
+            expected: CORE_VERSION.to_string_lossy().into_owned(),
+            found: core_version.to_string_lossy().into_owned(),
+        });
+    }
+
+    // Register into a scratch factory first so we can tell which names the plugin provides
+    let mut registered = AnimalFactory::empty();
+    ((*declaration).register)(&mut registered);
+
+    let animals = registered.names().map(str::to_string).collect();
+    factory.constructors.append(&mut registered.constructors);
+
+    std::mem::forget(library);
+
+    Ok(LoadedPlugin {
+        path: path.to_path_buf(),
+        animals,
+    })
+}
+
+/// Loads every shared library (`*.so` on Linux) in `dir`, in file name order, and registers
+/// their animals in `factory`. Stops at the first plugin that fails to load, and then registers
+/// none of them: `factory` is only changed when every plugin loaded.
+///
+/// # Safety
+///
+/// Every library in `dir` must uphold the requirements of [`load_plugin`].
+pub unsafe fn load_plugins_from_dir(
+    factory: &mut AnimalFactory,
+    dir: &Path,
+) -> Result<Vec<LoadedPlugin>, PluginError> {
+    let read_dir_error = |source| PluginError::ReadDir {
+        dir: dir.to_path_buf(),
+        source,
+    };
+
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(read_dir_error)? {
+        let path = entry.map_err(read_dir_error)?.path();
+        let is_library = path
+            .extension()
+            .is_some_and(|extension| extension == std::env::consts::DLL_EXTENSION);
+        if is_library && path.is_file() {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let mut registered = AnimalFactory::empty();
+    let loaded = paths
+        .iter()
+        .map(|path| load_plugin(&mut registered, path))
+        .collect::<Result<Vec<_>, _>>()?;
+    factory.constructors.append(&mut registered.constructors);
+    Ok(loaded)
+}
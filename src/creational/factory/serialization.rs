@@ -0,0 +1,221 @@
+// Animal serialization
+// A Box<dyn Animal> is written as its tag, the name it is registered under (Animal::kind), and is
+// read back by creating that name through an AnimalFactory. So a heterogeneous Vec<Box<dyn Animal>>
+// round-trips as long as the reading side has every kind registered, and a tag it doesn't know is
+// reported as an error instead of being dropped. to_json() and to_bytes() check every kind against
+// the factory too, an animal that only has the default kind() would be written but could never be
+// read back. The Serialize impl for embedding animals in other serde structures has no factory to
+// check against.
+//
+// JSON:   [{"kind":"dog"},{"kind":"cat"}]
+// Binary: b"ANML", format version (u8), animal count (LEB128), then per animal the tag length
+//         (LEB128) followed by the UTF-8 tag
+
+use std::error::Error;
+use std::fmt;
+
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
+
+use super::{Animal, AnimalFactory};
+
+const MAGIC: &[u8; 4] = b"ANML";
+const BINARY_VERSION: u8 = 1;
+
+#[derive(Debug)]
+pub enum SerializationError {
+    Json(serde_json::Error),
+    // Binary input that isn't in the format described above
+    InvalidBinary(String),
+    // The kind of the animal at `index` isn't registered in the factory
+    UnknownKind { index: usize, kind: String },
+}
+
+impl fmt::Display for SerializationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SerializationError::Json(err) => write!(f, "invalid animal JSON: {err}"),
+            SerializationError::InvalidBinary(reason) => {
+                write!(f, "invalid animal binary: {reason}")
+            }
+            SerializationError::UnknownKind { index, kind } => {
+                write!(f, "animal #{index} has unknown kind `{kind}`")
+            }
+        }
+    }
+}
+
+impl Error for SerializationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SerializationError::Json(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for SerializationError {
+    fn from(err: serde_json::Error) -> Self {
+        SerializationError::Json(err)
+    }
+}
+
+// Lets animals be embedded in any serde structure, e.g. a config struct holding Vec<Box<dyn Animal>>
+impl Serialize for dyn Animal {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut record = serializer.serialize_struct("Animal", 1)?;
+        record.serialize_field("kind", self.kind())?;
+        record.end()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AnimalRecord {
+    kind: String,
+}
+
+fn create_all<'a>(
+    factory: &AnimalFactory,
+    tags: impl IntoIterator<Item = &'a str>,
+) -> Result<Vec<Box<dyn Animal>>, SerializationError> {
+    tags.into_iter()
+        .enumerate()
+        .map(|(index, tag)| {
+            factory
+                .create(tag)
+                .map_err(|_| SerializationError::UnknownKind {
+                    index,
+                    kind: tag.to_string(),
+                })
+        })
+        .collect()
+}
+
+// Only writes what the factory can read back
+fn check_kinds(
+    factory: &AnimalFactory,
+    animals: &[Box<dyn Animal>],
+) -> Result<(), SerializationError> {
+    match animals
+        .iter()
+        .position(|animal| !factory.is_registered(animal.kind()))
+    {
+        Some(index) => Err(SerializationError::UnknownKind {
+            index,
+            kind: animals[index].kind().to_string(),
+        }),
+        None => Ok(()),
+    }
+}
+
+pub fn to_json(
+    factory: &AnimalFactory,
+    animals: &[Box<dyn Animal>],
+) -> Result<String, SerializationError> {
+    check_kinds(factory, animals)?;
+    Ok(serde_json::to_string(animals)?)
+}
+
+pub fn from_json(
+    factory: &AnimalFactory,
+    json: &str,
+) -> Result<Vec<Box<dyn Animal>>, SerializationError> {
+    let records: Vec<AnimalRecord> = serde_json::from_str(json)?;
+    create_all(factory, records.iter().map(|record| record.kind.as_str()))
+}
+
+pub fn to_bytes(
+    factory: &AnimalFactory,
+    animals: &[Box<dyn Animal>],
+) -> Result<Vec<u8>, SerializationError> {
+    check_kinds(factory, animals)?;
+    let mut bytes = MAGIC.to_vec();
+    bytes.push(BINARY_VERSION);
+    write_len(&mut bytes, animals.len());
+    for animal in animals {
+        write_len(&mut bytes, animal.kind().len());
+        bytes.extend_from_slice(animal.kind().as_bytes());
+    }
+    Ok(bytes)
+}
+
+pub fn from_bytes(
+    factory: &AnimalFactory,
+    bytes: &[u8],
+) -> Result<Vec<Box<dyn Animal>>, SerializationError> {
+    let mut reader = Reader { bytes };
+
+    if reader.take(MAGIC.len())? != MAGIC {
+        return Err(invalid("missing ANML header"));
+    }
+    let version = reader.take(1)?[0];
+    if version != BINARY_VERSION {
+        return Err(invalid(format!("unsupported format version {version}")));
+    }
+
+    let count = reader.read_len()?;
+    // Every animal takes at least one byte, so a larger count can only come from corrupt input
+    if count > reader.bytes.len() {
+        return Err(invalid(format!("{count} animals don't fit in the input")));
+    }
+
+    let mut tags = Vec::with_capacity(count);
+    for _ in 0..count {
+        let len = reader.read_len()?;
+        let tag = std::str::from_utf8(reader.take(len)?)
+            .map_err(|_| invalid("animal kind is not valid UTF-8"))?;
+        tags.push(tag);
+    }
+    if !reader.bytes.is_empty() {
+        return Err(invalid("trailing bytes after the last animal"));
+    }
+
+    create_all(factory, tags)
+}
+
+fn invalid(reason: impl Into<String>) -> SerializationError {
+    SerializationError::InvalidBinary(reason.into())
+}
+
+fn write_len(bytes: &mut Vec<u8>, mut len: usize) {
+    loop {
+        let byte = (len & 0x7f) as u8;
+        len >>= 7;
+        if len == 0 {
+            bytes.push(byte);
+            return;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], SerializationError> {
+        if self.bytes.len() < len {
+            return Err(invalid("unexpected end of input"));
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn read_len(&mut self) -> Result<usize, SerializationError> {
+        let mut len = 0usize;
+        for shift in (0..usize::BITS).step_by(7) {
+            let byte = self.take(1)?[0];
+            len |= usize::from(byte & 0x7f)
+                .checked_shl(shift)
+                .filter(|value| value >> shift == usize::from(byte & 0x7f))
+                .ok_or_else(|| invalid("length overflows usize"))?;
+            if byte & 0x80 == 0 {
+                return Ok(len);
+            }
+        }
+        Err(invalid("length overflows usize"))
+    }
+}
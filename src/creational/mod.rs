@@ -0,0 +1,14 @@
+// Creational patterns are primarily concerned with object creation and instantiation.
+
+#[cfg(feature = "abstract-factory")]
+pub mod abstract_factory;
+#[cfg(feature = "builder")]
+pub mod builder;
+#[cfg(feature = "dependency-injection")]
+pub mod dependency_injection;
+#[cfg(feature = "factory")]
+pub mod factory;
+#[cfg(feature = "prototype")]
+pub mod prototype;
+#[cfg(feature = "singleton")]
+pub mod singleton;
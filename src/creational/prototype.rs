@@ -0,0 +1,170 @@
+// Creational Pattern - Prototype
+// All prototype classes should have a common interface that makes it possible to copy objects
+//
+// get_clone() returns a Box<dyn Prototype> instead of Self, which keeps the trait object safe: a
+// Vec<Box<dyn Prototype>> of different types can be copied without knowing what is in it. Every
+// Clone type is a prototype through the blanket impl, and is(), downcast_ref(), downcast_mut() and
+// downcast() on dyn Prototype, and on dyn Prototype + Send + Sync, get the concrete type back, like
+// they do on dyn Any.
+//
+// Box<dyn Prototype> is deliberately not Clone. The blanket impl would make the box a prototype of
+// its own, and get_clone() on it would return a box in a box that no longer downcasts to the type
+// inside. Copy it with get_clone() instead, called on the dyn Prototype: through a &Box<dyn
+// Prototype> method resolution picks the blanket impl for the reference, so go through as_ref().
+//
+// The registry module keeps named templates to clone from, and the deep module clones whole
+// Rc<RefCell<_>> graphs.
+
+pub mod deep;
+pub mod registry;
+
+pub use deep::{DeepClone, Person};
+pub use registry::PrototypeRegistry;
+
+use std::any::Any;
+use std::fmt;
+use std::io::{self, Write};
+use std::rc::Rc;
+
+pub trait Prototype: Any {
+    fn get_clone(&self) -> Box<dyn Prototype>;
+
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<T: Clone + Any> Prototype for T {
+    fn get_clone(&self) -> Box<dyn Prototype> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+// Like the downcasts of std::any, for the plain trait object and its Send + Sync variant
+macro_rules! impl_downcasts {
+    ($object:ty) => {
+        impl $object {
+            pub fn is<T: Any>(&self) -> bool {
+                self.as_any().is::<T>()
+            }
+
+            pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+                self.as_any().downcast_ref()
+            }
+
+            pub fn downcast_mut<T: Any>(&mut self) -> Option<&mut T> {
+                self.as_any_mut().downcast_mut()
+            }
+
+            // The box is handed back when it holds another type
+            pub fn downcast<T: Any>(self: Box<Self>) -> Result<Box<T>, Box<$object>> {
+                if self.is::<T>() {
+                    Ok(self
+                        .into_any()
+                        .downcast()
+                        .expect("the type was checked by is()"))
+                } else {
+                    Err(self)
+                }
+            }
+        }
+
+        // Like dyn Any, the concrete type isn't known here
+        impl fmt::Debug for $object {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct("Prototype").finish_non_exhaustive()
+            }
+        }
+    };
+}
+
+impl_downcasts!(dyn Prototype);
+impl_downcasts!(dyn Prototype + Send + Sync);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Human {
+    pub name: String,
+    pub age: i32,
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let mut human1 = Human {
+        name: "Chethan".to_string(),
+        age: 21,
+    };
+
+    let human2 = human1
+        .get_clone()
+        .downcast::<Human>()
+        .expect("a Human clones into a Human");
+
+    human1.age = 22;
+
+    writeln!(out, "{human1:?} {human2:?}")?;
+
+    // Copied without knowing what is in it
+    let prototypes: Vec<Box<dyn Prototype>> = vec![
+        Box::new(human1),
+        Box::new("a String is a prototype too".to_string()),
+    ];
+    let clones: Vec<Box<dyn Prototype>> = prototypes
+        .iter()
+        .map(|prototype| prototype.as_ref().get_clone())
+        .collect();
+    for clone in &clones {
+        if let Some(human) = clone.downcast_ref::<Human>() {
+            writeln!(out, "Cloned human: {human:?}")?;
+        } else if let Some(text) = clone.downcast_ref::<String>() {
+            writeln!(out, "Cloned string: {text:?}")?;
+        }
+    }
+
+    // A fresh admin from the registry, with its own name
+    let registry = PrototypeRegistry::new();
+    match registry.create_with("default-admin", |admin: &mut Human| {
+        admin.name = "Alice".to_string();
+    }) {
+        Ok(admin) => writeln!(out, "From template: {admin:?}")?,
+        Err(err) => writeln!(out, "{err}")?,
+    }
+    writeln!(out, "Templates: {:?}", registry.names())?;
+
+    // Alice and Bob are friends of each other, and both of them are friends with Carol
+    let alice = Person::new("Alice", 30);
+    let bob = Person::new("Bob", 31);
+    let carol = Person::new("Carol", 32);
+    alice.borrow_mut().friends = vec![Rc::clone(&bob), Rc::clone(&carol)];
+    bob.borrow_mut().friends = vec![Rc::clone(&alice), Rc::clone(&carol)];
+
+    let alice_clone = alice.deep_clone();
+    let bob_clone = Rc::clone(&alice_clone.borrow().friends[0]);
+    bob_clone.borrow().friends[1].borrow_mut().human.name = "Caroline".to_string();
+    writeln!(out, "{:?}", alice_clone.borrow())?;
+    writeln!(out, "{:?}", bob_clone.borrow())?;
+    writeln!(out, "Original: {:?}", alice.borrow())?;
+    writeln!(
+        out,
+        "Cycle kept: {}",
+        Rc::ptr_eq(&bob_clone.borrow().friends[0], &alice_clone)
+    )?;
+
+    // Break the cycles, or the Rcs never get dropped
+    for person in [&alice, &bob, &alice_clone, &bob_clone] {
+        person.borrow_mut().friends.clear();
+    }
+    Ok(())
+}
@@ -0,0 +1,145 @@
+// Deep prototypes
+// get_clone() of a type that holds Rc<RefCell<_>> copies the Rc, so the clone shares its sub-objects
+// with the original. deep_clone() copies the whole object graph instead, and keeps its shape:
+//
+// - Two Rcs that point at the same node in the original point at the same new node in the clone.
+// - Cycles are copied as cycles. Every node is cloned once, the second visit finds its clone.
+//
+// A type takes part by implementing DeepClone::relink(). It is called on a plain clone of the value,
+// whose Rc fields still point into the original graph, and relinks every one of them to its copy:
+//
+//     impl DeepClone for Person {
+//         fn relink(&mut self, cloner: &mut Cloner) {
+//             self.friends.relink(cloner);
+//         }
+//     }
+//
+// Rc<RefCell<T>>, Weak<RefCell<T>>, Option and Vec implement it already. Nodes are relinked from a
+// work list rather than by recursion, so long chains don't overflow the stack. A Weak that points at
+// a node no Rc of the graph reaches ends up dangling in the clone, nothing in it owns that node.
+// The original nodes are borrowed while they are cloned, so none of them may be mutably borrowed.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::{Rc, Weak};
+
+use super::Human;
+
+pub trait DeepClone: Clone {
+    // Called on a fresh clone of self, to point its Rc fields at their copies
+    fn relink(&mut self, cloner: &mut Cloner);
+
+    fn deep_clone(&self) -> Self {
+        let mut cloner = Cloner {
+            clones: HashMap::new(),
+            pending: Vec::new(),
+        };
+        let mut clone = self.clone();
+        clone.relink(&mut cloner);
+        while let Some(relink) = cloner.pending.pop() {
+            relink(&mut cloner);
+        }
+        clone
+    }
+}
+
+type Relink = Box<dyn FnOnce(&mut Cloner)>;
+
+// Keeps track of the nodes that were cloned during one deep_clone()
+pub struct Cloner {
+    // Address of the original node, and its Rc<RefCell<T>> clone
+    clones: HashMap<*const (), Box<dyn Any>>,
+    // Clones whose fields still point into the original graph
+    pending: Vec<Relink>,
+}
+
+impl Cloner {
+    fn clone_rc<T: DeepClone + 'static>(&mut self, original: &Rc<RefCell<T>>) -> Rc<RefCell<T>> {
+        let address = Rc::as_ptr(original) as *const ();
+        if let Some(clone) = self.clones.get(&address) {
+            let clone = clone
+                .downcast_ref::<Rc<RefCell<T>>>()
+                .expect("a node has one type");
+            return Rc::clone(clone);
+        }
+
+        let clone = Rc::new(RefCell::new(original.borrow().clone()));
+        self.clones.insert(address, Box::new(Rc::clone(&clone)));
+        let pending = Rc::clone(&clone);
+        self.pending
+            .push(Box::new(move |cloner| pending.borrow_mut().relink(cloner)));
+        clone
+    }
+}
+
+impl<T: DeepClone + 'static> DeepClone for Rc<RefCell<T>> {
+    fn relink(&mut self, cloner: &mut Cloner) {
+        *self = cloner.clone_rc(self);
+    }
+}
+
+impl<T: DeepClone + 'static> DeepClone for Weak<RefCell<T>> {
+    fn relink(&mut self, cloner: &mut Cloner) {
+        if let Some(original) = self.upgrade() {
+            *self = Rc::downgrade(&cloner.clone_rc(&original));
+        }
+    }
+}
+
+impl<T: DeepClone> DeepClone for Option<T> {
+    fn relink(&mut self, cloner: &mut Cloner) {
+        if let Some(value) = self {
+            value.relink(cloner);
+        }
+    }
+}
+
+impl<T: DeepClone> DeepClone for Vec<T> {
+    fn relink(&mut self, cloner: &mut Cloner) {
+        for value in self {
+            value.relink(cloner);
+        }
+    }
+}
+
+// A Human with friends, who may be friends with each other and with them
+#[derive(Clone)]
+pub struct Person {
+    pub human: Human,
+    pub friends: Vec<Rc<RefCell<Person>>>,
+}
+
+impl Person {
+    pub fn new(name: &str, age: i32) -> Rc<RefCell<Person>> {
+        Rc::new(RefCell::new(Person {
+            human: Human {
+                name: name.to_string(),
+                age,
+            },
+            friends: Vec::new(),
+        }))
+    }
+}
+
+impl DeepClone for Person {
+    fn relink(&mut self, cloner: &mut Cloner) {
+        self.friends.relink(cloner);
+    }
+}
+
+// Friends by name only, the derived Debug would go round the cycles forever
+impl fmt::Debug for Person {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let friends: Vec<String> = self
+            .friends
+            .iter()
+            .map(|friend| friend.borrow().human.name.clone())
+            .collect();
+        f.debug_struct("Person")
+            .field("human", &self.human)
+            .field("friends", &friends)
+            .finish()
+    }
+}
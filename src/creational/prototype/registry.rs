@@ -0,0 +1,159 @@
+// Prototype registry
+// Named, preconfigured prototypes. Callers ask for a template by name and get a fresh clone, with
+// their own overrides applied in the same call:
+//
+//     let alice: Human = registry.create_with("default-admin", |human: &mut Human| {
+//         human.name = "Alice".to_string();
+//     })?;
+//
+// The templates sit behind a RwLock, so one registry can be shared between threads, e.g. by the
+// tests that seed their fixtures from it, and templates can be listed and replaced while it is in
+// use. A clone never sees a later replacement of its template.
+
+use std::any::{self, Any};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use super::{Human, Prototype};
+
+pub type Template = Box<dyn Prototype + Send + Sync>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrototypeRegistryError {
+    UnknownTemplate(String),
+    // The template isn't of the type the caller asked for
+    WrongType {
+        name: String,
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for PrototypeRegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrototypeRegistryError::UnknownTemplate(name) => {
+                write!(f, "no template is registered under `{name}`")
+            }
+            PrototypeRegistryError::WrongType { name, expected } => {
+                write!(f, "template `{name}` is not a {expected}")
+            }
+        }
+    }
+}
+
+impl Error for PrototypeRegistryError {}
+
+pub struct PrototypeRegistry {
+    templates: RwLock<BTreeMap<String, Template>>,
+}
+
+impl PrototypeRegistry {
+    // Registry with the built-in templates ("default-admin" and "default-user")
+    pub fn new() -> Self {
+        let registry = Self::empty();
+        registry
+            .register(
+                "default-admin",
+                Human {
+                    name: "admin".to_string(),
+                    age: 30,
+                },
+            )
+            .register(
+                "default-user",
+                Human {
+                    name: "user".to_string(),
+                    age: 18,
+                },
+            );
+        registry
+    }
+
+    pub fn empty() -> Self {
+        Self {
+            templates: RwLock::new(BTreeMap::new()),
+        }
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, BTreeMap<String, Template>> {
+        self.templates
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, BTreeMap<String, Template>> {
+        self.templates
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // Registering a name twice replaces the previous template
+    pub fn register<T>(&self, name: impl Into<String>, template: T) -> &Self
+    where
+        T: Prototype + Send + Sync,
+    {
+        self.write().insert(name.into(), Box::new(template));
+        self
+    }
+
+    // Like register(), for a name that must exist already. Returns the old template
+    pub fn replace<T>(&self, name: &str, template: T) -> Result<Template, PrototypeRegistryError>
+    where
+        T: Prototype + Send + Sync,
+    {
+        let mut templates = self.write();
+        let current = templates
+            .get_mut(name)
+            .ok_or_else(|| PrototypeRegistryError::UnknownTemplate(name.to_string()))?;
+        Ok(std::mem::replace(current, Box::new(template)))
+    }
+
+    pub fn unregister(&self, name: &str) -> Option<Template> {
+        self.write().remove(name)
+    }
+
+    pub fn is_registered(&self, name: &str) -> bool {
+        self.read().contains_key(name)
+    }
+
+    // Registered names in alphabetical order
+    pub fn names(&self) -> Vec<String> {
+        self.read().keys().cloned().collect()
+    }
+
+    pub fn get(&self, name: &str) -> Result<Box<dyn Prototype>, PrototypeRegistryError> {
+        self.read()
+            .get(name)
+            .map(|template| template.as_ref().get_clone())
+            .ok_or_else(|| PrototypeRegistryError::UnknownTemplate(name.to_string()))
+    }
+
+    // A clone of the template as its concrete type
+    pub fn create<T: Any>(&self, name: &str) -> Result<T, PrototypeRegistryError> {
+        self.create_with(name, |_: &mut T| {})
+    }
+
+    pub fn create_with<T, F>(&self, name: &str, overrides: F) -> Result<T, PrototypeRegistryError>
+    where
+        T: Any,
+        F: FnOnce(&mut T),
+    {
+        let mut clone =
+            self.get(name)?
+                .downcast::<T>()
+                .map_err(|_| PrototypeRegistryError::WrongType {
+                    name: name.to_string(),
+                    expected: any::type_name::<T>(),
+                })?;
+        overrides(&mut clone);
+        Ok(*clone)
+    }
+}
+
+impl Default for PrototypeRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,198 @@
+// Creational Pattern - Singleton
+// This pattern involves a single class (called Singleton) which is responsible to create an object
+// while making sure that only single object(instance) gets created. This class provides a way to
+// access its only object which can be accessed directly using get_instance() method without need
+// to instantiate the object of the class which should create one if there isn't one created already.
+
+// The Java version guards getInstance() with `synchronized`. In Rust, OnceLock gives the same
+// thread-safe, one-time initialization without locking on every access.
+//
+// OnceLock can't retry a failed initialization, and once set it stays set for the whole process.
+// Global<T, E> is the generic facility for the cases where that matters:
+//
+//     static DATABASE: Global<Database, ConnectError> = Global::new(Database::connect);
+//
+//     let database = DATABASE.get()?;
+//
+// - The first get() runs the initializer, every later one returns the same Arc<T>. Callers that
+//   arrive while it runs wait for it instead of running it a second time.
+// - An initializer that returns Err stores nothing, so the next get() tries again. So does one that
+//   panics.
+// - An initializer must not get() the instance it is constructing, not even through other code:
+//   callers wait for the running initializer, so it would wait for itself forever. The same goes
+//   for reset(), and for remove() or teardown() of the Multiton key under construction. Getting
+//   another Global or another key is fine, as long as no two initializers wait for each other.
+// - reset() drops the instance so that the next get() constructs a new one. It exists for tests
+//   only, behind the test-util feature, so that one test doesn't see the state of another. Handing
+//   out Arc<T> instead of &'static T is what makes it safe, the old instance lives as long as
+//   someone still holds it.
+//
+// The multiton module has one such instance per key.
+
+pub mod multiton;
+
+pub use multiton::Multiton;
+
+use std::convert::Infallible;
+use std::io::{self, Write};
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex, OnceLock, PoisonError, RwLock};
+
+#[derive(Debug)]
+pub struct Singleton {
+    // Private field so that this struct cannot be instantiated outside this module
+    _private: (),
+}
+
+static INSTANCE: OnceLock<Singleton> = OnceLock::new();
+
+impl Singleton {
+    // Only one thread can initialize the instance and this is the only way to get hold of it
+    pub fn get_instance() -> &'static Singleton {
+        INSTANCE.get_or_init(|| Singleton { _private: () })
+    }
+}
+
+// The instance of a Global, or of one key of a Multiton
+struct Slot<T> {
+    instance: RwLock<Option<Arc<T>>>,
+    // Held while the initializer runs, so that it runs on one thread at a time
+    initializing: Mutex<()>,
+}
+
+impl<T> Slot<T> {
+    const fn new() -> Self {
+        Self {
+            instance: RwLock::new(None),
+            initializing: Mutex::new(()),
+        }
+    }
+
+    fn get(&self) -> Option<Arc<T>> {
+        self.instance
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    fn get_or_try_init<E>(&self, init: impl FnOnce() -> Result<T, E>) -> Result<Arc<T>, E> {
+        if let Some(instance) = self.get() {
+            return Ok(instance);
+        }
+
+        // A panicking initializer poisons the lock but leaves no instance behind, so the next
+        // caller can go on and retry
+        let _initializing = self
+            .initializing
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        // Another thread may have finished while this one was waiting
+        if let Some(instance) = self.get() {
+            return Ok(instance);
+        }
+        let instance = Arc::new(init()?);
+        *self
+            .instance
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = Some(Arc::clone(&instance));
+        Ok(instance)
+    }
+
+    // Waits for an initializer that is running
+    fn take(&self) -> Option<Arc<T>> {
+        let _initializing = self
+            .initializing
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        self.instance
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+    }
+}
+
+pub struct Global<T, E = Infallible, F = fn() -> Result<T, E>> {
+    init: F,
+    slot: Slot<T>,
+    error: PhantomData<fn() -> E>,
+}
+
+impl<T, E, F> Global<T, E, F>
+where
+    F: Fn() -> Result<T, E>,
+{
+    pub const fn new(init: F) -> Self {
+        Self {
+            init,
+            slot: Slot::new(),
+            error: PhantomData,
+        }
+    }
+
+    pub fn get(&self) -> Result<Arc<T>, E> {
+        self.slot.get_or_try_init(&self.init)
+    }
+
+    // The instance, without constructing it
+    pub fn try_get(&self) -> Option<Arc<T>> {
+        self.slot.get()
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.try_get().is_some()
+    }
+
+    // Waits for an initializer that is running, and returns the instance it dropped
+    #[cfg(any(test, feature = "test-util"))]
+    pub fn reset(&self) -> Option<Arc<T>> {
+        self.slot.take()
+    }
+}
+
+impl<T, F> Global<T, Infallible, F>
+where
+    F: Fn() -> Result<T, Infallible>,
+{
+    // get() for initializers that can't fail
+    pub fn instance(&self) -> Arc<T> {
+        match self.get() {
+            Ok(instance) => instance,
+            Err(never) => match never {},
+        }
+    }
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let first = Singleton::get_instance();
+    let second = Singleton::get_instance();
+
+    writeln!(out, "Same instance: {}", std::ptr::eq(first, second))?;
+
+    // The first connection attempt fails, the second get() retries it
+    let attempts = Mutex::new(0);
+    let connection = Global::new(|| {
+        let mut attempts = attempts.lock().unwrap();
+        *attempts += 1;
+        if *attempts == 1 {
+            Err(format!("attempt {attempts}: connection refused"))
+        } else {
+            Ok(format!("connected on attempt {attempts}"))
+        }
+    });
+    for _ in 0..3 {
+        match connection.get() {
+            Ok(connection) => writeln!(out, "{connection}")?,
+            Err(err) => writeln!(out, "{err}")?,
+        }
+    }
+
+    // One settings object per tenant, torn down newest first
+    let tenants =
+        Multiton::new(|tenant: &String| Ok::<_, Infallible>(format!("settings of {tenant}")));
+    for tenant in ["acme", "globex", "acme", "initech"] {
+        let settings = tenants.instance(tenant);
+        writeln!(out, "{tenant}: {settings}")?;
+    }
+    writeln!(out, "Tenants: {:?}", tenants.keys())?;
+    writeln!(out, "Torn down: {:?}", tenants.teardown())
+}
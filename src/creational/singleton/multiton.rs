@@ -0,0 +1,272 @@
+// Multiton
+// One shared instance per key instead of one for the whole process, e.g. a connection pool per
+// database URL or a settings object per tenant. Every key is a Global of its own: the first get()
+// of a key runs the initializer with that key, a failed one is retried, and callers of other keys
+// don't wait for it.
+//
+//     static POOLS: Multiton<String, Pool, PoolError> = Multiton::new(Pool::connect);
+//
+//     let pool = POOLS.get("postgres://primary")?;
+//
+// keys() and instances() list the instances that exist, in the order they were constructed.
+// remove() tears down one key and teardown() every key, and dropping the Multiton tears it down
+// too. Teardown releases the instances in reverse order of construction, so an instance that was
+// constructed with the help of an older one is gone before it. A caller that still holds an Arc
+// keeps its instance alive, it is dropped when the last of them goes. Drop impls may use the
+// Multiton again, no lock is held while they run.
+//
+// get() doesn't hold the lock of the map while the initializer runs, so the entry it works on may
+// be removed or torn down in the meantime. An initializer never starts for an entry that was taken
+// out of the map, the caller starts over with a new entry instead, and remove() and teardown() wait
+// for one that is running. So every instance is either in the map or was handed to remove() or
+// teardown(). A key whose initializer fails is taken out of the map too.
+
+use std::borrow::Borrow;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use super::Slot;
+
+struct Entry<V> {
+    slot: Slot<V>,
+    // Construction order, set by the initializer
+    constructed: AtomicU64,
+    // Taken out of the map, set before the slot is emptied
+    removed: AtomicBool,
+}
+
+impl<V> Entry<V> {
+    fn new() -> Self {
+        Self {
+            slot: Slot::new(),
+            constructed: AtomicU64::new(0),
+            removed: AtomicBool::new(false),
+        }
+    }
+
+    // Waits for an initializer that is running
+    fn release(&self) -> Option<Arc<V>> {
+        self.removed.store(true, Ordering::SeqCst);
+        self.slot.take()
+    }
+}
+
+// Why get() didn't get an instance
+enum Attempt<E> {
+    Failed(E),
+    // The entry was taken out of the map, start over
+    Removed,
+}
+
+pub struct Multiton<K, V, E = Infallible, F = fn(&K) -> Result<V, E>> {
+    init: F,
+    entries: Mutex<BTreeMap<K, Arc<Entry<V>>>>,
+    constructed: AtomicU64,
+    error: PhantomData<fn() -> E>,
+}
+
+impl<K, V, E, F> Multiton<K, V, E, F> {
+    pub const fn new(init: F) -> Self {
+        Self {
+            init,
+            entries: Mutex::new(BTreeMap::new()),
+            constructed: AtomicU64::new(0),
+            error: PhantomData,
+        }
+    }
+
+    fn entries(&self) -> MutexGuard<'_, BTreeMap<K, Arc<Entry<V>>>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // Keys of the torn down instances, in the order they were released
+    pub fn teardown(&self) -> Vec<K> {
+        let entries = std::mem::take(&mut *self.entries());
+        release(entries.into_iter().collect())
+    }
+}
+
+impl<K, V, E, F> Multiton<K, V, E, F>
+where
+    K: Ord,
+    F: Fn(&K) -> Result<V, E>,
+{
+    pub fn get<Q>(&self, key: &Q) -> Result<Arc<V>, E>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ToOwned<Owned = K> + ?Sized,
+    {
+        loop {
+            let entry = {
+                let mut entries = self.entries();
+                match entries.get(key) {
+                    Some(entry) => Arc::clone(entry),
+                    None => {
+                        let entry = Arc::new(Entry::new());
+                        entries.insert(key.to_owned(), Arc::clone(&entry));
+                        entry
+                    }
+                }
+            };
+            // The lock is released, so that other keys are constructed in parallel. removed is set
+            // before the slot is emptied, and the slot waits for a running initializer, so an
+            // instance constructed here is never left behind outside the map.
+            let instance = entry.slot.get_or_try_init(|| {
+                if entry.removed.load(Ordering::SeqCst) {
+                    return Err(Attempt::Removed);
+                }
+                match (self.init)(&key.to_owned()) {
+                    Ok(instance) => {
+                        let order = self.constructed.fetch_add(1, Ordering::SeqCst);
+                        entry.constructed.store(order, Ordering::SeqCst);
+                        Ok(instance)
+                    }
+                    Err(err) => {
+                        // Callers that wait for this entry start over with a new one
+                        self.forget(key, &entry);
+                        Err(Attempt::Failed(err))
+                    }
+                }
+            });
+            match instance {
+                Ok(instance) => return Ok(instance),
+                Err(Attempt::Failed(err)) => return Err(err),
+                Err(Attempt::Removed) => continue,
+            }
+        }
+    }
+
+    // Takes the entry out of the map, unless it was replaced already
+    fn forget<Q>(&self, key: &Q, entry: &Arc<Entry<V>>)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut entries = self.entries();
+        if entries
+            .get(key)
+            .is_some_and(|current| Arc::ptr_eq(current, entry))
+        {
+            entries.remove(key);
+        }
+        entry.removed.store(true, Ordering::SeqCst);
+    }
+
+    // The instance of the key, without constructing it
+    pub fn try_get<Q>(&self, key: &Q) -> Option<Arc<V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let entry = self.entries().get(key).map(Arc::clone)?;
+        entry.slot.get()
+    }
+
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.try_get(key).is_some()
+    }
+
+    // Tears down the instance of one key
+    pub fn remove<Q>(&self, key: &Q) -> Option<Arc<V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let entry = self.entries().remove(key)?;
+        entry.release()
+    }
+}
+
+impl<K, V, E, F> Multiton<K, V, E, F>
+where
+    K: Ord + Clone,
+{
+    // Oldest first, keys whose initializer failed or still runs are left out
+    pub fn instances(&self) -> Vec<(K, Arc<V>)> {
+        let entries: Vec<(K, Arc<Entry<V>>)> = self
+            .entries()
+            .iter()
+            .map(|(key, entry)| (key.clone(), Arc::clone(entry)))
+            .collect();
+        let mut constructed: Vec<(u64, K, Arc<V>)> = entries
+            .into_iter()
+            .filter_map(|(key, entry)| {
+                let instance = entry.slot.get()?;
+                Some((entry.constructed.load(Ordering::SeqCst), key, instance))
+            })
+            .collect();
+        constructed.sort_by_key(|(order, ..)| *order);
+        constructed
+            .into_iter()
+            .map(|(_, key, instance)| (key, instance))
+            .collect()
+    }
+
+    pub fn keys(&self) -> Vec<K> {
+        self.instances().into_iter().map(|(key, _)| key).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.instances().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<K, V, F> Multiton<K, V, Infallible, F>
+where
+    K: Ord,
+    F: Fn(&K) -> Result<V, Infallible>,
+{
+    // get() for initializers that can't fail
+    pub fn instance<Q>(&self, key: &Q) -> Arc<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ToOwned<Owned = K> + ?Sized,
+    {
+        match self.get(key) {
+            Ok(instance) => instance,
+            Err(never) => match never {},
+        }
+    }
+}
+
+impl<K, V, E, F> Drop for Multiton<K, V, E, F> {
+    fn drop(&mut self) {
+        let entries = std::mem::take(
+            self.entries
+                .get_mut()
+                .unwrap_or_else(PoisonError::into_inner),
+        );
+        release(entries.into_iter().collect());
+    }
+}
+
+// Newest first, outside of any lock
+fn release<K, V>(entries: Vec<(K, Arc<Entry<V>>)>) -> Vec<K> {
+    let mut constructed: Vec<(u64, K, Arc<V>)> = entries
+        .into_iter()
+        .filter_map(|(key, entry)| {
+            let instance = entry.release()?;
+            Some((entry.constructed.load(Ordering::SeqCst), key, instance))
+        })
+        .collect();
+    constructed.sort_by_key(|(order, ..)| Reverse(*order));
+    constructed
+        .into_iter()
+        .map(|(_, key, instance)| {
+            drop(instance);
+            key
+        })
+        .collect()
+}
@@ -0,0 +1,11 @@
+// Creational patterns are primarily concerned with object creation and instantiation.
+// Structural patterns are concerned with the composition of objects to create larger structures.
+// Behavioral patterns deal with the communication and collaboration between objects to achieve specific behaviors.
+
+// Every pattern lives behind a cargo feature of the same name (e.g. `factory`, `proxy`,
+// `chain-of-responsibility`), so dependants can pull in only the patterns they use.
+
+pub mod behavioral;
+pub mod catalog;
+pub mod creational;
+pub mod structural;
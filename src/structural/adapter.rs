@@ -0,0 +1,348 @@
+// Structural Pattern - Adapter
+// The Adapter acts as a wrapper between two objects. It catches calls for one object and
+// transforms them to format and interface recognizable by the second object.
+// Main intention is not to implement the Target trait to InCompatible
+
+use std::io::{self, Write};
+
+// Example 1 -
+
+pub trait Target {
+    fn request(&self) -> String;
+}
+
+pub fn call(target: impl Target, out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "{}", target.request())
+}
+
+pub struct Compatible;
+
+impl Compatible {
+    pub fn specific_request(&self) -> String {
+        "I'm comptaible object".to_string()
+    }
+}
+
+impl Target for Compatible {
+    fn request(&self) -> String {
+        self.specific_request()
+    }
+}
+
+// Adaptee - Object which is incompatible
+pub struct InCompatible;
+
+impl InCompatible {
+    pub fn specific_request(&self) -> String {
+        "I'm incomptaible object".to_string()
+    }
+}
+
+// Object which makes incompatible objects as compatible
+pub struct Adapter {
+    pub adaptee: InCompatible,
+}
+
+impl Target for Adapter {
+    fn request(&self) -> String {
+        self.adaptee.specific_request()
+    }
+}
+
+pub fn demo_target(out: &mut dyn Write) -> io::Result<()> {
+    call(Compatible, out)?;
+
+    // Gives error as this is incompatible (doesn't want to implement Target trait)
+    // let incompatible = call(InCompatible, out);
+
+    // So build a wrapper around InCompatible
+    let adaptor = Adapter {
+        adaptee: InCompatible,
+    };
+    call(adaptor, out)
+}
+
+// Example 2 -
+
+/*
+ * Core Trait that defines a basic Rocket Ship
+ */
+pub trait RocketShip {
+    fn turn_on(&self, out: &mut dyn Write) -> io::Result<()>;
+    fn turn_off(&self, out: &mut dyn Write) -> io::Result<()>;
+    fn blast_off(&self, out: &mut dyn Write) -> io::Result<()>;
+    fn fly(&self, out: &mut dyn Write) -> io::Result<()>;
+}
+
+/*
+ * Basic struct for a NASA Ship
+ */
+pub struct NASAShip;
+
+/*
+ * Implement RocketShip trait to add functionality to NASAShip
+ */
+impl RocketShip for NASAShip {
+    fn turn_on(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "NASA Ship is turning on.")
+    }
+
+    fn turn_off(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "NASA Ship is turning off.")
+    }
+
+    fn blast_off(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "NASA Ship is blasting off.")
+    }
+
+    fn fly(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "NASA Ship is flying away.")
+    }
+}
+
+/*
+ * Uh oh, here is our problem. It's the amazingly advanced SpaceX ship that our
+ * astronaut doesn't know how to pilot.
+ */
+pub trait SpaceXShip {
+    fn ignition(&self, out: &mut dyn Write) -> io::Result<()>;
+    fn on(&self, out: &mut dyn Write) -> io::Result<()>;
+    fn off(&self, out: &mut dyn Write) -> io::Result<()>;
+    fn launch(&self, out: &mut dyn Write) -> io::Result<()>;
+    fn fly(&self, out: &mut dyn Write) -> io::Result<()>;
+}
+
+/*
+ * Basic struct for a SpaceX Dragon rocket ship
+ */
+pub struct SpaceXDragon;
+
+/*
+ * Implement the SpaceX trait to add functionality to the Space X Dragon
+ */
+impl SpaceXShip for SpaceXDragon {
+    fn ignition(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Turning Dragon's ignition.")
+    }
+
+    fn on(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Turning on the Dragon.")
+    }
+
+    fn off(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Turning off the Dragon.")
+    }
+
+    fn launch(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Launching the Dragon")
+    }
+
+    fn fly(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "The Dragon is flying away.")
+    }
+}
+
+/*
+ * Borrowed and boxed ships are SpaceX ships too, so the adapter can hold them
+ */
+impl<S: SpaceXShip + ?Sized> SpaceXShip for &S {
+    fn ignition(&self, out: &mut dyn Write) -> io::Result<()> {
+        (**self).ignition(out)
+    }
+
+    fn on(&self, out: &mut dyn Write) -> io::Result<()> {
+        (**self).on(out)
+    }
+
+    fn off(&self, out: &mut dyn Write) -> io::Result<()> {
+        (**self).off(out)
+    }
+
+    fn launch(&self, out: &mut dyn Write) -> io::Result<()> {
+        (**self).launch(out)
+    }
+
+    fn fly(&self, out: &mut dyn Write) -> io::Result<()> {
+        (**self).fly(out)
+    }
+}
+
+impl<S: SpaceXShip + ?Sized> SpaceXShip for Box<S> {
+    fn ignition(&self, out: &mut dyn Write) -> io::Result<()> {
+        (**self).ignition(out)
+    }
+
+    fn on(&self, out: &mut dyn Write) -> io::Result<()> {
+        (**self).on(out)
+    }
+
+    fn off(&self, out: &mut dyn Write) -> io::Result<()> {
+        (**self).off(out)
+    }
+
+    fn launch(&self, out: &mut dyn Write) -> io::Result<()> {
+        (**self).launch(out)
+    }
+
+    fn fly(&self, out: &mut dyn Write) -> io::Result<()> {
+        (**self).fly(out)
+    }
+}
+
+/*
+ * Uh oh, the new SpaceXDragon doesn't implement the RocketShip interface. We
+ * need to create an adapter that does.
+ */
+/*
+ * Adapter to adapt anything that implements SpaceXShip to the RocketShip trait
+ */
+pub struct SpaceXAdapter<S> {
+    pub ship: S,
+}
+
+/*
+ * An adapter for ships whose type is only known at runtime, e.g. a mixed fleet
+ */
+pub type DynSpaceXAdapter = SpaceXAdapter<Box<dyn SpaceXShip>>;
+
+impl<S: SpaceXShip> SpaceXAdapter<S> {
+    pub fn new(ship: S) -> Self {
+        Self { ship }
+    }
+}
+
+impl DynSpaceXAdapter {
+    pub fn boxed(ship: impl SpaceXShip + 'static) -> Self {
+        Self::new(Box::new(ship))
+    }
+}
+
+impl<S: SpaceXShip> From<S> for SpaceXAdapter<S> {
+    fn from(ship: S) -> Self {
+        Self::new(ship)
+    }
+}
+
+/*
+ * SpaceX Adapter that adds RocketShip traits to any SpaceXShip
+ */
+impl<S: SpaceXShip> RocketShip for SpaceXAdapter<S> {
+    fn turn_on(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.ship.ignition(out)?;
+        self.ship.on(out)
+    }
+
+    fn turn_off(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.ship.off(out)
+    }
+
+    fn blast_off(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.ship.launch(out)
+    }
+
+    fn fly(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.ship.fly(out)
+    }
+}
+
+/*
+ * Conversions into the adapter for every SpaceXShip, e.g. Starship.into_rocket_ship()
+ */
+pub trait IntoRocketShip: SpaceXShip + Sized {
+    fn into_rocket_ship(self) -> SpaceXAdapter<Self> {
+        SpaceXAdapter::new(self)
+    }
+
+    fn as_rocket_ship(&self) -> SpaceXAdapter<&Self> {
+        SpaceXAdapter::new(self)
+    }
+
+    fn into_dyn_rocket_ship(self) -> DynSpaceXAdapter
+    where
+        Self: 'static,
+    {
+        SpaceXAdapter::boxed(self)
+    }
+}
+
+impl<S: SpaceXShip> IntoRocketShip for S {}
+
+/*
+ * A Starship, the next SpaceX ship. SpaceXAdapter flies it as it is.
+ */
+pub struct Starship;
+
+impl SpaceXShip for Starship {
+    fn ignition(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Lighting Starship's Raptor engines.")
+    }
+
+    fn on(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Turning on the Starship.")
+    }
+
+    fn off(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Turning off the Starship.")
+    }
+
+    fn launch(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Launching the Starship")
+    }
+
+    fn fly(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "The Starship is flying away.")
+    }
+}
+
+/*
+ * Basic function to pilot ships that implement the RocketShip trait
+ */
+pub fn pilot(ship: &impl RocketShip, out: &mut dyn Write) -> io::Result<()> {
+    ship.turn_on(out)?;
+    ship.blast_off(out)?;
+    ship.fly(out)?;
+    ship.turn_off(out)?;
+    writeln!(out, "\n")
+}
+
+pub fn demo_rocket_ship(out: &mut dyn Write) -> io::Result<()> {
+    // Create a new NASAShip
+    let saturn5 = NASAShip;
+
+    // Let's fly our NASAShip
+    writeln!(out, "Piloting the Saturn 5.")?;
+    pilot(&saturn5, out)?;
+
+    // Create a Dragon
+    let dragon = SpaceXDragon;
+
+    // Uh oh, our pilot function doesn't recognize this ship...
+    // pilot(&dragon, out); <-- Gives a compile time error.
+
+    // Let's Adapt our SpaceXDragon ship
+    let dragon_adapter = SpaceXAdapter { ship: dragon };
+
+    // Now we can pilot the Dragon!
+    writeln!(out, "Piloting the Dragon Adapter.")?;
+    pilot(&dragon_adapter, out)?;
+
+    // The same adapter flies the Starship, no new code needed
+    writeln!(out, "Piloting the Starship Adapter.")?;
+    pilot(&Starship.into_rocket_ship(), out)?;
+
+    // A fleet of different ships, adapted behind Box<dyn SpaceXShip>
+    let fleet: Vec<DynSpaceXAdapter> = vec![
+        SpaceXDragon.into_dyn_rocket_ship(),
+        SpaceXAdapter::boxed(Starship),
+    ];
+    for ship in &fleet {
+        writeln!(out, "Piloting a ship of the fleet.")?;
+        pilot(ship, out)?;
+    }
+    Ok(())
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    demo_target(out)?;
+    demo_rocket_ship(out)
+}
@@ -0,0 +1,74 @@
+// Structural Pattern - Composite
+// Composite is a structural design pattern that lets you compose objects into tree structures
+// and then work with these structures as if they were individual objects.
+
+use std::io::{self, Write};
+
+pub trait Component {
+    fn search(&self, keyword: &str, out: &mut dyn Write) -> io::Result<()>;
+}
+
+pub struct File {
+    name: &'static str,
+}
+
+impl File {
+    pub fn new(name: &'static str) -> Self {
+        Self { name }
+    }
+}
+
+impl Component for File {
+    fn search(&self, keyword: &str, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Searching for {} in file {}", keyword, self.name)
+    }
+}
+
+pub struct Folder {
+    name: &'static str,
+    inner_components: Vec<Box<dyn Component>>,
+}
+
+impl Folder {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            inner_components: vec![],
+        }
+    }
+
+    pub fn insert_component(&mut self, component: impl Component + 'static) {
+        self.inner_components.push(Box::new(component));
+    }
+}
+
+impl Component for Folder {
+    fn search(&self, keyword: &str, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(
+            out,
+            "Searching recursively for keyword {} in folder {}",
+            keyword, self.name
+        )?;
+
+        for component in self.inner_components.iter() {
+            component.search(keyword, out)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let file1 = File::new("file1");
+    let file2 = File::new("file2");
+    let file3 = File::new("file3");
+
+    let mut folder1 = Folder::new("folder1");
+    let mut folder2 = Folder::new("folder2");
+
+    folder1.insert_component(file1);
+    folder2.insert_component(file2);
+    folder2.insert_component(file3);
+    folder2.insert_component(folder1);
+
+    folder2.search("rose", out)
+}
@@ -0,0 +1,51 @@
+// Structural Pattern - Decorator
+// Decorator is a structural pattern that allows adding new behaviors to objects dynamically by
+// placing them inside special wrapper objects, called decorators.
+
+use std::io::{self, Write};
+
+pub trait Color {
+    fn fill(&self, out: &mut dyn Write) -> io::Result<()>;
+}
+
+// Concrete component of the base trait
+pub struct Black;
+
+impl Color for Black {
+    fn fill(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Black color")
+    }
+}
+
+// Concrete decorator which wraps (has-a) the base trait object and is-a Color itself
+pub struct PatternDecorator {
+    colored: Box<dyn Color>,
+}
+
+impl PatternDecorator {
+    pub fn new(colored: Box<dyn Color>) -> Self {
+        Self { colored }
+    }
+
+    fn add_pattern(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Pattern")
+    }
+}
+
+impl Color for PatternDecorator {
+    fn fill(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.colored.fill(out)?;
+        self.add_pattern(out)
+    }
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let black = Black;
+    let pattern = PatternDecorator::new(Box::new(Black));
+
+    writeln!(out, "\nStyle: Solid")?;
+    black.fill(out)?;
+
+    writeln!(out, "\nStyle: Pattern")?;
+    pattern.fill(out)
+}
@@ -0,0 +1,58 @@
+// Structural Pattern - Facade
+// Facade design pattern is a type of structural design pattern. The main objective of facade
+// design pattern is to provide a simple interface before a complex system. So that the user or
+// client can access the simple interface without knowing the complexity of its sub-system.
+// Facade design patterns are mostly used to hide the dependencies involved in a system from the user.
+
+use std::io::{self, Write};
+
+#[derive(Clone, Copy)]
+pub struct PlaceOrder;
+
+impl PlaceOrder {
+    pub fn place_order(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Order placed")
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Payment;
+
+impl Payment {
+    pub fn make_payment(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Payment received")
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Delivery;
+
+impl Delivery {
+    pub fn delivery(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Order Delivered")
+    }
+}
+
+// Facade
+pub struct Operation {
+    pub order: PlaceOrder,
+    pub payment: Payment,
+    pub delivery: Delivery,
+}
+
+impl Operation {
+    pub fn complete_order(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.order.place_order(out)?;
+        self.payment.make_payment(out)?;
+        self.delivery.delivery(out)
+    }
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let operation = Operation {
+        order: PlaceOrder,
+        payment: Payment,
+        delivery: Delivery,
+    };
+    operation.complete_order(out)
+}
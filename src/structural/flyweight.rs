@@ -0,0 +1,105 @@
+// Structural Pattern - FlyWeight
+// Lets you fit more objects into the available RAM by sharing common parts of state between
+// multiple objects, instead of storing all of the data in each object individually.
+
+// Consider there are only 2 types of book and depending on type, distributor also change.
+// Hence we have limited options for both type_of_book and distributor.
+
+// struct Book {
+//     name: String,
+//     prize: i32,
+//     type_of_book: String,
+//     distributor: String,
+// }
+
+// So split them into separate struct
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+pub struct Book {
+    pub name: String,
+    pub prize: i32,
+    pub book_type: BookType,
+}
+
+#[derive(Clone)]
+pub struct BookType {
+    pub type_of_book: String,
+    pub distributor: String,
+}
+
+// FlyWeight Factory (Like a cache)
+#[derive(Default)]
+pub struct BookFactory {
+    book_type: HashMap<String, BookType>,
+}
+
+impl BookFactory {
+    pub fn get_book_type(&mut self, type_of_book: String, distributor: String) -> BookType {
+        self.book_type
+            .entry(type_of_book.clone())
+            .or_insert(BookType {
+                type_of_book,
+                distributor,
+            })
+            .clone()
+    }
+}
+
+#[derive(Default)]
+pub struct Store {
+    books: Vec<Book>,
+}
+
+impl Store {
+    pub fn add_book(
+        &mut self,
+        book_factory: &mut BookFactory,
+        name: String,
+        prize: i32,
+        type_of_book: String,
+        distributor: String,
+    ) {
+        let book_type = book_factory.get_book_type(type_of_book, distributor);
+        let book = Book {
+            name,
+            prize,
+            book_type,
+        };
+        self.books.push(book);
+    }
+
+    pub fn display_books(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.books.iter().try_for_each(|book| {
+            let r = format!(
+                "{} {} {} {}",
+                book.name, book.prize, book.book_type.type_of_book, book.book_type.distributor
+            );
+            writeln!(out, "{r}")
+        })
+    }
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let mut store = Store::default();
+    let mut book_factory = BookFactory::default();
+    for i in 0..5 {
+        store.add_book(
+            &mut book_factory,
+            format!("book{}", i + 1),
+            i + 10,
+            "Action".to_string(),
+            "distributor1".to_string(),
+        );
+        store.add_book(
+            &mut book_factory,
+            format!("book{}", i + 2),
+            i + 20,
+            "Adventure".to_string(),
+            "distributor2".to_string(),
+        )
+    }
+
+    store.display_books(out)
+}
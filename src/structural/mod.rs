@@ -0,0 +1,14 @@
+// Structural patterns are concerned with the composition of objects to create larger structures.
+
+#[cfg(feature = "adapter")]
+pub mod adapter;
+#[cfg(feature = "composite")]
+pub mod composite;
+#[cfg(feature = "decorator")]
+pub mod decorator;
+#[cfg(feature = "facade")]
+pub mod facade;
+#[cfg(feature = "flyweight")]
+pub mod flyweight;
+#[cfg(feature = "proxy")]
+pub mod proxy;
@@ -0,0 +1,114 @@
+// Structural Pattern - Proxy
+// Proxy is a structural design pattern that provides an object that acts as a substitute for a
+// real service object used by a client. A proxy receives client requests, does some work (access
+// control, caching, etc.) and then passes the request to a service object.
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+pub trait Server {
+    fn handle_request(&mut self, url: &str, method: &str) -> (u16, String);
+}
+
+pub struct Application;
+
+impl Server for Application {
+    fn handle_request(&mut self, url: &str, method: &str) -> (u16, String) {
+        if url == "/app/status" && method == "GET" {
+            return (200, "Ok".into());
+        }
+
+        if url == "/create/user" && method == "POST" {
+            return (201, "User Created".into());
+        }
+
+        (404, "Not Ok".into())
+    }
+}
+
+/// NGINX server is a proxy to an application server.
+pub struct NginxServer {
+    application: Application,
+    max_allowed_requests: u32,
+    rate_limiter: HashMap<String, u32>,
+}
+
+impl NginxServer {
+    pub fn new() -> Self {
+        Self {
+            application: Application,
+            max_allowed_requests: 2,
+            rate_limiter: HashMap::default(),
+        }
+    }
+
+    pub fn check_rate_limiting(&mut self, url: &str) -> bool {
+        let rate = self.rate_limiter.entry(url.to_string()).or_insert(1);
+
+        if *rate > self.max_allowed_requests {
+            return false;
+        }
+
+        *rate += 1;
+        true
+    }
+}
+
+impl Default for NginxServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Server for NginxServer {
+    fn handle_request(&mut self, url: &str, method: &str) -> (u16, String) {
+        if !self.check_rate_limiting(url) {
+            return (403, "Not Allowed".into());
+        }
+
+        self.application.handle_request(url, method)
+    }
+}
+
+pub fn demo(out: &mut dyn Write) -> io::Result<()> {
+    let app_status = &"/app/status".to_string();
+    let create_user = &"/create/user".to_string();
+
+    // Instead of calling handle_request through Application, we call it through Nginx
+    let mut nginx = NginxServer::new();
+
+    let (code, body) = nginx.handle_request(app_status, "GET");
+    writeln!(
+        out,
+        "Url: {}\nHttpCode: {}\nBody: {}\n",
+        app_status, code, body
+    )?;
+
+    let (code, body) = nginx.handle_request(app_status, "GET");
+    writeln!(
+        out,
+        "Url: {}\nHttpCode: {}\nBody: {}\n",
+        app_status, code, body
+    )?;
+
+    let (code, body) = nginx.handle_request(app_status, "GET");
+    writeln!(
+        out,
+        "Url: {}\nHttpCode: {}\nBody: {}\n",
+        app_status, code, body
+    )?;
+
+    let (code, body) = nginx.handle_request(create_user, "POST");
+    writeln!(
+        out,
+        "Url: {}\nHttpCode: {}\nBody: {}\n",
+        create_user, code, body
+    )?;
+
+    let (code, body) = nginx.handle_request(create_user, "GET");
+    writeln!(
+        out,
+        "Url: {}\nHttpCode: {}\nBody: {}\n",
+        create_user, code, body
+    )
+}
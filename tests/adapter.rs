@@ -0,0 +1,73 @@
+use std::io::{self, Write};
+
+use design_patterns::structural::adapter::{
+    pilot, DynSpaceXAdapter, IntoRocketShip, SpaceXAdapter, SpaceXDragon, SpaceXShip, Starship,
+};
+
+fn capture(f: impl FnOnce(&mut Vec<u8>) -> io::Result<()>) -> String {
+    let mut out = Vec::new();
+    f(&mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+// A ship from another crate, piloted without any adapter code of its own
+struct Falcon;
+
+impl SpaceXShip for Falcon {
+    fn ignition(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "falcon ignition")
+    }
+
+    fn on(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "falcon on")
+    }
+
+    fn off(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "falcon off")
+    }
+
+    fn launch(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "falcon launch")
+    }
+
+    fn fly(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "falcon fly")
+    }
+}
+
+#[test]
+fn generic_adapter_pilots_any_spacex_ship() {
+    assert_eq!(
+        capture(|out| pilot(&Falcon.into_rocket_ship(), out)),
+        "falcon ignition\nfalcon on\nfalcon launch\nfalcon fly\nfalcon off\n\n\n"
+    );
+    assert_eq!(
+        capture(|out| pilot(&SpaceXAdapter::new(Starship), out)),
+        "Lighting Starship's Raptor engines.\nTurning on the Starship.\nLaunching the Starship\n\
+         The Starship is flying away.\nTurning off the Starship.\n\n\n"
+    );
+}
+
+#[test]
+fn every_adapter_flavour_flies_the_same() {
+    let expected = capture(|out| pilot(&SpaceXAdapter { ship: SpaceXDragon }, out));
+
+    let borrowed = SpaceXDragon;
+    let boxed: Box<dyn SpaceXShip> = Box::new(SpaceXDragon);
+    let adapters: Vec<DynSpaceXAdapter> = vec![
+        SpaceXAdapter::boxed(SpaceXDragon),
+        SpaceXDragon.into_dyn_rocket_ship(),
+        SpaceXAdapter::from(boxed),
+    ];
+    for adapter in &adapters {
+        assert_eq!(capture(|out| pilot(adapter, out)), expected);
+    }
+    assert_eq!(
+        capture(|out| pilot(&borrowed.as_rocket_ship(), out)),
+        expected
+    );
+    assert_eq!(
+        capture(|out| pilot(&SpaceXAdapter::from(SpaceXDragon), out)),
+        expected
+    );
+}
@@ -0,0 +1,173 @@
+use design_patterns::creational::builder::{
+    KubernetesClusterBuilder, KubernetesVersion, NodePoolBuilder, Taint, TaintEffect, Violation,
+    MAX_POOL_NODES, MAX_VERSION,
+};
+
+fn builder(name: &str, version: &str) -> KubernetesClusterBuilder {
+    KubernetesClusterBuilder::new(name.to_string(), version.to_string())
+}
+
+fn pool(name: &str) -> NodePoolBuilder {
+    NodePoolBuilder::new(name.to_string(), "e2-standard-4".to_string())
+}
+
+#[test]
+fn valid_spec_builds() {
+    let cluster = builder("prod-eu-1", "1.30")
+        .auto_upgrade(true)
+        .node_pool(&mut pool("general"))
+        .build()
+        .unwrap();
+
+    assert_eq!(cluster.name(), "prod-eu-1");
+    assert_eq!(cluster.version(), "1.30");
+    assert!(cluster.auto_upgrade());
+    assert_eq!(cluster.node_pools().len(), 1);
+    assert_eq!(cluster.node_pool("general").unwrap().max_nodes(), 3);
+}
+
+#[test]
+fn every_violation_is_reported() {
+    let err = builder("", "1.29.1")
+        .auto_upgrade(true)
+        .node_pool(&mut pool("Pool"))
+        .build()
+        .unwrap_err();
+
+    let fields: Vec<&str> = err.violations().iter().map(Violation::field).collect();
+    assert_eq!(fields, ["name", "auto_upgrade", "node_pools[0].name"]);
+    assert_eq!(
+        err.violations()[1],
+        Violation::AutoUpgradeRequiresChannel(KubernetesVersion::release(1, 29, 1))
+    );
+}
+
+#[test]
+fn versions_are_parsed_and_range_checked() {
+    let err = builder("dev", "banana").build().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid cluster spec\n  version: `banana` is not a version, expected MAJOR.MINOR or MAJOR.MINOR.PATCH"
+    );
+
+    for version in ["1.029", "1.x.0", "1..3", "1.2.3.4"] {
+        let err = builder("dev", version).build().unwrap_err();
+        assert!(
+            matches!(err.violations(), [Violation::InvalidVersion { .. }]),
+            "{version}"
+        );
+    }
+
+    let err = builder("dev", "1.26.9").build().unwrap_err();
+    assert_eq!(
+        err.violations(),
+        [Violation::UnsupportedVersion(KubernetesVersion::release(
+            1, 26, 9
+        ))]
+    );
+    assert!(builder("dev", &MAX_VERSION.to_string()).build().is_ok());
+}
+
+#[test]
+fn names_follow_dns_1123_labels() {
+    let too_long = "a".repeat(64);
+    for name in ["-dev", "dev-", "dev.eu", "DEV", too_long.as_str()] {
+        assert!(builder(name, "1.28").build().is_err(), "{name}");
+    }
+    assert!(builder(&"a".repeat(63), "1.28").build().is_ok());
+    assert!(builder("0-dev-1", "1.28").build().is_ok());
+}
+
+#[test]
+fn typestate_builder_validates_like_the_runtime_one() {
+    use design_patterns::creational::builder::typestate::ClusterBuilder;
+
+    let cluster = ClusterBuilder::new()
+        .node_pool(&mut pool("general"))
+        .version("1.28".to_string())
+        .name("prod".to_string())
+        .build()
+        .unwrap();
+    assert_eq!((cluster.name(), cluster.version()), ("prod", "1.28"));
+
+    let err = ClusterBuilder::new()
+        .name("Prod".to_string())
+        .version("1.28.2".to_string())
+        .auto_upgrade(true)
+        .build()
+        .unwrap_err();
+    assert_eq!(err.violations().len(), 2);
+}
+
+#[test]
+fn node_pools_are_configured_by_nested_builders() {
+    let cluster = builder("prod", "1.30")
+        .node_pool(pool("system").autoscaling(1, 3).taint(Taint::new(
+            "CriticalAddonsOnly",
+            "true",
+            TaintEffect::NoSchedule,
+        )))
+        .node_pool(
+            pool("spot")
+                .autoscaling(0, 40)
+                .spot(true)
+                .label("tier", "batch")
+                .label("team", "ml")
+                .zone("europe-west1-b"),
+        )
+        .build()
+        .unwrap();
+
+    let names: Vec<&str> = cluster
+        .node_pools()
+        .iter()
+        .map(|pool| pool.name())
+        .collect();
+    assert_eq!(names, ["system", "spot"]);
+
+    let spot = cluster.node_pool("spot").unwrap();
+    assert!(spot.spot());
+    assert_eq!((spot.min_nodes(), spot.max_nodes()), (0, 40));
+    assert_eq!(spot.labels().keys().collect::<Vec<_>>(), ["team", "tier"]);
+    assert_eq!(spot.zones(), ["europe-west1-b"]);
+    assert_eq!(
+        cluster.node_pool("system").unwrap().taints()[0].to_string(),
+        "CriticalAddonsOnly=true:NoSchedule"
+    );
+}
+
+#[test]
+fn pool_names_are_unique_and_autoscaling_bounds_sane() {
+    let err = builder("prod", "1.30")
+        .node_pool(&mut pool("compute"))
+        .node_pool(pool("spot").autoscaling(4, 2))
+        .node_pool(pool("empty").autoscaling(0, 0))
+        .node_pool(pool("huge").max_nodes(MAX_POOL_NODES + 1))
+        .node_pool(pool("compute").zone("a").zone("a"))
+        .build()
+        .unwrap_err();
+
+    let fields: Vec<&str> = err.violations().iter().map(Violation::field).collect();
+    assert_eq!(
+        fields,
+        [
+            "node_pools[1].max_nodes",
+            "node_pools[2].max_nodes",
+            "node_pools[3].max_nodes",
+            "node_pools[4].name",
+            "node_pools[4].zones[1]",
+        ]
+    );
+    assert_eq!(
+        err.violations()[3],
+        Violation::DuplicatePoolName {
+            field: "node_pools[4].name".to_string(),
+            name: "compute".to_string(),
+            first: 0,
+        }
+    );
+    assert!(builder("prod", "1.30")
+        .node_pool(pool("scale-to-zero").autoscaling(0, MAX_POOL_NODES))
+        .build()
+        .is_ok());
+}
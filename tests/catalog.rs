@@ -0,0 +1,44 @@
+use design_patterns::catalog::{self, Category};
+
+#[test]
+fn every_category_has_patterns() {
+    for category in Category::ALL {
+        assert!(catalog::by_category(category).next().is_some());
+    }
+}
+
+#[test]
+fn related_patterns_are_in_the_catalog() {
+    for pattern in catalog::patterns() {
+        for related in pattern.related {
+            assert!(
+                catalog::find(related).is_some(),
+                "{} refers to unknown pattern {related}",
+                pattern.name
+            );
+        }
+    }
+}
+
+#[test]
+fn sources_exist() {
+    for pattern in catalog::patterns() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(pattern.source);
+        assert!(path.is_file(), "{} is missing", pattern.source);
+    }
+}
+
+#[test]
+fn search_is_case_insensitive() {
+    let names: Vec<_> = catalog::search("RAM").map(|pattern| pattern.name).collect();
+    assert_eq!(names, ["flyweight"]);
+}
+
+#[test]
+fn demos_run() {
+    for pattern in catalog::patterns() {
+        let mut out = Vec::new();
+        pattern.run_demo(&mut out).unwrap();
+        assert!(!out.is_empty(), "{} printed nothing", pattern.name);
+    }
+}
@@ -0,0 +1,85 @@
+use std::process::{Command, Output};
+
+use design_patterns::catalog;
+
+fn patterns(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_patterns"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8(output.stderr.clone()).unwrap()
+}
+
+#[test]
+fn list_prints_every_pattern_of_the_catalog() {
+    let output = patterns(&["list"]);
+    assert!(output.status.success());
+
+    let stdout = stdout(&output);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), catalog::patterns().len());
+    for (line, pattern) in lines.iter().zip(catalog::patterns()) {
+        assert!(line.starts_with(&format!("{} ", pattern.name)), "{line}");
+        assert!(line.ends_with(pattern.intent), "{line}");
+    }
+}
+
+#[test]
+fn run_prints_the_demo_output() {
+    let output = patterns(&["run", "proxy"]);
+    assert!(output.status.success());
+
+    let mut expected = Vec::new();
+    catalog::find("proxy")
+        .unwrap()
+        .run_demo(&mut expected)
+        .unwrap();
+    assert_eq!(stdout(&output), String::from_utf8(expected).unwrap());
+    assert!(stdout(&output).starts_with("Url: /app/status\nHttpCode: 200\n"));
+}
+
+#[test]
+fn unknown_pattern_exits_with_2() {
+    let output = patterns(&["run", "visitor"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(stdout(&output), "");
+    assert_eq!(
+        stderr(&output),
+        "Unknown pattern `visitor`. Run `patterns list` to see the available patterns.\n"
+    );
+}
+
+#[test]
+fn bad_arguments_print_the_usage() {
+    for args in [&[][..], &["run"], &["frobnicate", "proxy"]] {
+        let output = patterns(args);
+        assert_eq!(output.status.code(), Some(2), "{args:?}");
+        assert!(stderr(&output).starts_with("Usage:\n"), "{args:?}");
+    }
+
+    let output = patterns(&["--help"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("Usage:\n"));
+}
+
+#[test]
+fn closed_pipe_is_not_a_panic() {
+    let (reader, writer) = std::io::pipe().unwrap();
+    drop(reader);
+    for args in [&["list"][..], &["run", "proxy"], &["markdown"]] {
+        let output = Command::new(env!("CARGO_BIN_EXE_patterns"))
+            .args(args)
+            .stdout(writer.try_clone().unwrap())
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{args:?}");
+        assert_eq!(stderr(&output), "", "{args:?}");
+    }
+}
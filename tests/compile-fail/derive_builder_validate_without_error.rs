@@ -0,0 +1,9 @@
+use design_patterns::creational::builder::Builder;
+
+#[derive(Builder)]
+#[builder(validate = ProbeBuilder::validate)]
+pub struct Probe {
+    path: String,
+}
+
+fn main() {}
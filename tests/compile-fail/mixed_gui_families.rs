@@ -0,0 +1,7 @@
+use design_patterns::creational::abstract_factory::{
+    LinuxFactory, LinuxMenu, LinuxTextbox, WinButton, Window,
+};
+
+fn main() {
+    let _window = Window::new(LinuxFactory {}, WinButton {}, LinuxMenu {}, LinuxTextbox::default());
+}
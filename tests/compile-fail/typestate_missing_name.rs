@@ -0,0 +1,5 @@
+use design_patterns::creational::builder::typestate::ClusterBuilder;
+
+fn main() {
+    let _cluster = ClusterBuilder::new().version("1.30".to_string()).build();
+}
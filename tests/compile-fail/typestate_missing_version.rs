@@ -0,0 +1,8 @@
+use design_patterns::creational::builder::typestate::ClusterBuilder;
+
+fn main() {
+    let _cluster = ClusterBuilder::new()
+        .name("prod".to_string())
+        .auto_upgrade(true)
+        .build();
+}
@@ -0,0 +1,9 @@
+use design_patterns::creational::builder::typestate::ClusterBuilder;
+
+fn main() {
+    let _cluster = ClusterBuilder::new()
+        .name("prod".to_string())
+        .version("1.30".to_string())
+        .name("staging".to_string())
+        .build();
+}
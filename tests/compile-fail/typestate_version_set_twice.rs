@@ -0,0 +1,9 @@
+use design_patterns::creational::builder::typestate::ClusterBuilder;
+
+fn main() {
+    let _cluster = ClusterBuilder::new()
+        .version("1.29".to_string())
+        .version("1.30".to_string())
+        .name("prod".to_string())
+        .build();
+}
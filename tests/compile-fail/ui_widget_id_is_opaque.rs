@@ -0,0 +1,6 @@
+use design_patterns::creational::abstract_factory::ui::{Layout, WidgetId};
+
+fn main() {
+    // Ids only come from the Ui that owns the widget
+    let _layout = Layout::Widget(WidgetId(7));
+}
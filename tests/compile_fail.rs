@@ -0,0 +1,6 @@
+// Guarantees that only hold if some code doesn't compile
+#[test]
+fn compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/compile-fail/*.rs");
+}
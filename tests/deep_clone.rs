@@ -0,0 +1,146 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::rc::{Rc, Weak};
+
+use design_patterns::creational::prototype::deep::Cloner;
+use design_patterns::creational::prototype::{DeepClone, Person};
+
+type Node = Rc<RefCell<Person>>;
+
+// Every node reachable from the root, in the order a breadth-first walk finds them, and for each
+// node its name and the indices of its friends
+fn topology(root: &Node) -> (Vec<Node>, Vec<(String, Vec<usize>)>) {
+    let mut nodes = vec![Rc::clone(root)];
+    let mut index = HashMap::from([(Rc::as_ptr(root), 0)]);
+    let mut edges = Vec::new();
+    let mut queue = VecDeque::from([Rc::clone(root)]);
+    while let Some(node) = queue.pop_front() {
+        let node = node.borrow();
+        let friends = node
+            .friends
+            .iter()
+            .map(|friend| {
+                *index.entry(Rc::as_ptr(friend)).or_insert_with(|| {
+                    nodes.push(Rc::clone(friend));
+                    queue.push_back(Rc::clone(friend));
+                    nodes.len() - 1
+                })
+            })
+            .collect();
+        edges.push((node.human.name.clone(), friends));
+    }
+    (nodes, edges)
+}
+
+fn befriend(person: &Node, friends: &[&Node]) {
+    person
+        .borrow_mut()
+        .friends
+        .extend(friends.iter().map(|friend| Rc::clone(friend)));
+}
+
+#[test]
+fn clone_has_the_same_topology_and_no_shared_nodes() {
+    let alice = Person::new("Alice", 30);
+    let bob = Person::new("Bob", 31);
+    let carol = Person::new("Carol", 32);
+    let dave = Person::new("Dave", 33);
+    // Cycles of two and three, a self-loop and a node reached over three paths
+    befriend(&alice, &[&bob, &carol]);
+    befriend(&bob, &[&alice, &carol, &dave]);
+    befriend(&carol, &[&dave]);
+    befriend(&dave, &[&dave, &alice]);
+
+    let clone = alice.deep_clone();
+    let (original_nodes, original_edges) = topology(&alice);
+    let (cloned_nodes, cloned_edges) = topology(&clone);
+
+    assert_eq!(original_nodes.len(), 4);
+    assert_eq!(cloned_edges, original_edges);
+    assert!(cloned_nodes.iter().all(|cloned| original_nodes
+        .iter()
+        .all(|original| !Rc::ptr_eq(cloned, original))));
+
+    // Changing a shared node of the clone shows through every path to it, and not in the original
+    cloned_nodes[2].borrow_mut().human.name = "Caroline".to_string();
+    assert_eq!(
+        clone.borrow().friends[0].borrow().friends[1]
+            .borrow()
+            .human
+            .name,
+        "Caroline"
+    );
+    assert_eq!(carol.borrow().human.name, "Carol");
+    assert_eq!(topology(&alice).1, original_edges);
+}
+
+#[test]
+fn roots_sharing_nodes_are_cloned_together() {
+    let shared = Person::new("Shared", 40);
+    let left = Person::new("Left", 1);
+    let right = Person::new("Right", 2);
+    befriend(&left, &[&shared]);
+    befriend(&right, &[&shared]);
+
+    // Cloned in one go the two roots keep sharing, cloned one by one they don't
+    let together = vec![Rc::clone(&left), Rc::clone(&right)].deep_clone();
+    assert!(Rc::ptr_eq(
+        &together[0].borrow().friends[0],
+        &together[1].borrow().friends[0]
+    ));
+
+    let (left_clone, right_clone) = (left.deep_clone(), right.deep_clone());
+    assert!(!Rc::ptr_eq(
+        &left_clone.borrow().friends[0],
+        &right_clone.borrow().friends[0]
+    ));
+}
+
+#[derive(Clone)]
+struct TreeNode {
+    label: &'static str,
+    parent: Weak<RefCell<TreeNode>>,
+    children: Vec<Rc<RefCell<TreeNode>>>,
+}
+
+impl DeepClone for TreeNode {
+    fn relink(&mut self, cloner: &mut Cloner) {
+        self.parent.relink(cloner);
+        self.children.relink(cloner);
+    }
+}
+
+fn tree_node(label: &'static str, parent: Option<&Rc<RefCell<TreeNode>>>) -> Rc<RefCell<TreeNode>> {
+    let node = Rc::new(RefCell::new(TreeNode {
+        label,
+        parent: parent.map(Rc::downgrade).unwrap_or_default(),
+        children: Vec::new(),
+    }));
+    if let Some(parent) = parent {
+        parent.borrow_mut().children.push(Rc::clone(&node));
+    }
+    node
+}
+
+#[test]
+fn weak_back_references_point_into_the_clone() {
+    let root = tree_node("root", None);
+    let child = tree_node("child", Some(&root));
+    tree_node("grandchild", Some(&child));
+
+    let clone = root.deep_clone();
+    let cloned_child = Rc::clone(&clone.borrow().children[0]);
+    let cloned_grandchild = Rc::clone(&cloned_child.borrow().children[0]);
+
+    assert!(clone.borrow().parent.upgrade().is_none());
+    assert!(Rc::ptr_eq(
+        &cloned_child.borrow().parent.upgrade().unwrap(),
+        &clone
+    ));
+    assert!(Rc::ptr_eq(
+        &cloned_grandchild.borrow().parent.upgrade().unwrap(),
+        &cloned_child
+    ));
+    assert!(!Rc::ptr_eq(&cloned_child, &child));
+    assert_eq!(cloned_grandchild.borrow().label, "grandchild");
+}
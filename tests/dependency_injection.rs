@@ -0,0 +1,123 @@
+use std::rc::Rc;
+
+use design_patterns::creational::dependency_injection::{Container, ResolveError};
+
+trait Clock {
+    fn now(&self) -> u64;
+}
+
+struct FixedClock;
+
+impl Clock for FixedClock {
+    fn now(&self) -> u64 {
+        42
+    }
+}
+
+struct Service {
+    clock: Rc<dyn Clock>,
+}
+
+struct RequestContext;
+
+struct A(#[allow(dead_code)] Rc<B>);
+struct B(#[allow(dead_code)] Rc<A>);
+
+#[test]
+fn singletons_are_shared_and_transients_are_not() {
+    let mut container = Container::new();
+    container
+        .singleton::<dyn Clock, _>(|_| Ok(Rc::new(FixedClock)))
+        .transient(|resolver| {
+            Ok(Rc::new(Service {
+                clock: resolver.resolve::<dyn Clock>()?,
+            }))
+        });
+
+    let first = container.resolve::<Service>().unwrap();
+    let second = container.resolve::<Service>().unwrap();
+
+    assert!(!Rc::ptr_eq(&first, &second));
+    assert!(Rc::ptr_eq(&first.clock, &second.clock));
+    assert_eq!(first.clock.now(), 42);
+}
+
+#[test]
+fn scoped_services_are_shared_within_a_scope() {
+    let mut container = Container::new();
+    container.scoped(|_| Ok(Rc::new(RequestContext)));
+
+    let scope = container.create_scope();
+    let first = scope.resolve::<RequestContext>().unwrap();
+    let second = scope.resolve::<RequestContext>().unwrap();
+    let other = container
+        .create_scope()
+        .resolve::<RequestContext>()
+        .unwrap();
+
+    assert!(Rc::ptr_eq(&first, &second));
+    assert!(!Rc::ptr_eq(&first, &other));
+    assert!(matches!(
+        container.resolve::<RequestContext>(),
+        Err(ResolveError::ScopeRequired { .. })
+    ));
+}
+
+#[test]
+fn singletons_cannot_capture_scoped_services() {
+    let mut container = Container::new();
+    container
+        .scoped(|_| Ok(Rc::new(RequestContext)))
+        .singleton(|resolver| {
+            resolver.resolve::<RequestContext>()?;
+            Ok(Rc::new(FixedClock))
+        });
+
+    let err = container
+        .create_scope()
+        .resolve::<FixedClock>()
+        .err()
+        .unwrap();
+    assert!(matches!(err, ResolveError::ScopeRequired { .. }));
+    assert_eq!(err.path(), [std::any::type_name::<FixedClock>()]);
+}
+
+#[test]
+fn missing_registration_reports_path() {
+    let mut container = Container::new();
+    container.transient(|resolver| {
+        Ok(Rc::new(Service {
+            clock: resolver.resolve::<dyn Clock>()?,
+        }))
+    });
+
+    let err = container.resolve::<Service>().err().unwrap();
+    assert_eq!(
+        err,
+        ResolveError::Missing {
+            service: std::any::type_name::<dyn Clock>(),
+            path: vec![std::any::type_name::<Service>()],
+        }
+    );
+}
+
+#[test]
+fn cycles_are_detected() {
+    let mut container = Container::new();
+    container
+        .transient(|resolver| Ok(Rc::new(A(resolver.resolve::<B>()?))))
+        .transient(|resolver| Ok(Rc::new(B(resolver.resolve::<A>()?))));
+
+    let err = container.resolve::<A>().err().unwrap();
+    let (a, b) = (std::any::type_name::<A>(), std::any::type_name::<B>());
+    assert_eq!(
+        err,
+        ResolveError::Cycle {
+            path: vec![a, b, a]
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        format!("dependency cycle: {a} -> {b} -> {a}")
+    );
+}
@@ -0,0 +1,52 @@
+use std::io::{self, Write};
+
+use design_patterns::creational::factory::{Animal, AnimalFactory, AnimalFactoryError};
+
+struct Cow;
+
+impl Animal for Cow {
+    fn speak(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Cow says: Moo!")
+    }
+
+    fn kind(&self) -> &str {
+        "cow"
+    }
+}
+
+#[test]
+fn registered_animals_are_created_by_name() {
+    let mut factory = AnimalFactory::new();
+    factory.register("cow", || Box::new(Cow));
+
+    assert_eq!(factory.names().collect::<Vec<_>>(), ["cat", "cow", "dog"]);
+
+    let mut out = Vec::new();
+    factory.create("cow").unwrap().speak(&mut out).unwrap();
+    assert_eq!(out, b"Cow says: Moo!\n");
+}
+
+#[test]
+fn unknown_animal_is_an_error() {
+    let factory = AnimalFactory::empty();
+
+    assert_eq!(
+        factory.create("dog").err(),
+        Some(AnimalFactoryError::UnknownAnimal("dog".to_string()))
+    );
+}
+
+// Written before Animal::kind() existed
+struct Parrot;
+
+impl Animal for Parrot {
+    fn speak(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Parrot says: Hello!")
+    }
+}
+
+#[test]
+fn kind_defaults_to_the_type_name() {
+    let parrot: Box<dyn Animal> = Box::new(Parrot);
+    assert_eq!(parrot.kind(), std::any::type_name::<Parrot>());
+}
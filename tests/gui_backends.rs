@@ -0,0 +1,94 @@
+// Snapshot tests of the rendering GUI families. To update a snapshot after an intended change,
+// run the test with UPDATE_SNAPSHOTS=1 and review the diff.
+
+use std::path::Path;
+
+use design_patterns::creational::abstract_factory::ansi::AnsiFactory;
+use design_patterns::creational::abstract_factory::html::HtmlFactory;
+use design_patterns::creational::abstract_factory::{
+    create_gui, Button, GUIFactory, GUIFamily, Labels, Window,
+};
+
+fn render(factory: &dyn GUIFactory) -> String {
+    let mut out = Vec::new();
+    create_gui(factory, &mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+fn assert_snapshot(name: &str, actual: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(name);
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::write(&path, actual).unwrap();
+    }
+    let expected = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(actual, expected, "snapshot {name} differs");
+}
+
+fn labels() -> Labels {
+    Labels {
+        button: "Save & Exit".to_string(),
+        menu: vec!["File".to_string(), "<Edit>".to_string()],
+        textbox: "Hello".to_string(),
+    }
+}
+
+#[test]
+fn ansi_window() {
+    let factory = AnsiFactory {
+        labels: labels(),
+        ..AnsiFactory::default()
+    };
+    assert_snapshot("ansi_window.txt", &render(&factory));
+}
+
+#[test]
+fn html_window() {
+    let factory = HtmlFactory::new("Snapshot".to_string(), labels());
+    assert_snapshot("html_window.html", &render(&factory));
+}
+
+#[test]
+fn static_window_matches_dynamic_rendering() {
+    let factory = HtmlFactory::new("Snapshot".to_string(), labels());
+    let expected = render(&factory);
+
+    let mut out = Vec::new();
+    Window::from_family(factory).render(&mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), expected);
+}
+
+// Drops the SGR colour escapes, which take no room on the terminal
+fn strip_colors(painted: &str) -> String {
+    let mut stripped = String::new();
+    let mut rest = painted;
+    while let Some(start) = rest.find("\x1b[") {
+        stripped.push_str(&rest[..start]);
+        let end = rest[start..].find('m').unwrap();
+        rest = &rest[start + end + 1..];
+    }
+    stripped + rest
+}
+
+#[test]
+fn ansi_boxes_fit_wide_labels_and_escape_control_characters() {
+    let factory = AnsiFactory {
+        labels: Labels {
+            button: "日本 e\u{301}\n\x1b[2J".to_string(),
+            ..Labels::default()
+        },
+        ..AnsiFactory::default()
+    };
+    let button = factory.button();
+    let mut out = Vec::new();
+    button.paint(&mut out).unwrap();
+
+    // 日本 takes two columns per character and the combining accent none
+    let content = "[ 日本 e\u{301}\\n\\u{1b}[2J ]";
+    let line = "─".repeat(23);
+    assert_eq!(
+        strip_colors(&String::from_utf8(out).unwrap()),
+        format!("┌{line}┐\n│ {content} │\n└{line}┘\n")
+    );
+}
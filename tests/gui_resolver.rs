@@ -0,0 +1,171 @@
+use std::io::{self, Write};
+
+use design_patterns::creational::abstract_factory::resolver::{
+    Capabilities, ColorSupport, FamilyError, FamilyResolver, Source,
+};
+use design_patterns::creational::abstract_factory::{
+    create_gui, Button, GUIFactory, LinuxFactory, Menu, Textbox,
+};
+
+fn capabilities(is_terminal: bool, colors: ColorSupport) -> Capabilities {
+    Capabilities {
+        is_terminal,
+        colors,
+        os: "linux".to_string(),
+    }
+}
+
+// Third-party family, only registered by the tests
+struct HighContrastFactory;
+
+struct HighContrastButton;
+
+impl Button for HighContrastButton {
+    fn paint(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "[[ OK ]]")
+    }
+}
+
+impl GUIFactory for HighContrastFactory {
+    fn create_button(&self) -> Box<dyn Button> {
+        Box::new(HighContrastButton)
+    }
+
+    fn create_menu(&self) -> Box<dyn Menu> {
+        LinuxFactory {}.create_menu()
+    }
+
+    fn create_textbox(&self) -> Box<dyn Textbox> {
+        LinuxFactory {}.create_textbox()
+    }
+}
+
+fn with_high_contrast() -> FamilyResolver {
+    let mut resolver = FamilyResolver::new();
+    resolver
+        .register("high-contrast", || Box::new(HighContrastFactory))
+        .detect("high-contrast", |capabilities| {
+            capabilities.is_terminal && capabilities.colors == ColorSupport::None
+        });
+    resolver
+}
+
+#[test]
+fn config_wins_over_environment_and_detection() {
+    let resolver = with_high_contrast();
+    let terminal = capabilities(true, ColorSupport::TrueColor);
+
+    let resolution = resolver
+        .resolve_with(Some("html"), Some("windows"), &terminal)
+        .unwrap();
+    assert_eq!(
+        (resolution.name.as_str(), resolution.source),
+        ("html", Source::Config)
+    );
+
+    let resolution = resolver
+        .resolve_with(Some(" "), Some("windows"), &terminal)
+        .unwrap();
+    assert_eq!(
+        (resolution.name.as_str(), resolution.source),
+        ("windows", Source::Environment)
+    );
+}
+
+#[test]
+fn detection_asks_the_latest_detector_first_then_falls_back() {
+    let resolver = with_high_contrast();
+    let resolve = |capabilities: Capabilities| {
+        let resolution = resolver.resolve_with(None, None, &capabilities).unwrap();
+        (resolution.name, resolution.source)
+    };
+
+    assert_eq!(
+        resolve(capabilities(true, ColorSupport::Basic)),
+        ("ansi".to_string(), Source::Detected)
+    );
+    assert_eq!(
+        resolve(capabilities(true, ColorSupport::None)),
+        ("high-contrast".to_string(), Source::Detected)
+    );
+    assert_eq!(
+        resolve(capabilities(false, ColorSupport::TrueColor)),
+        ("linux".to_string(), Source::Fallback)
+    );
+}
+
+#[test]
+fn fallback_follows_the_os_of_the_capabilities() {
+    let mut resolver = FamilyResolver::new();
+    let on = |os: &str| Capabilities {
+        os: os.to_string(),
+        ..capabilities(false, ColorSupport::None)
+    };
+    let resolve = |resolver: &FamilyResolver, os| {
+        let resolution = resolver.resolve_with(None, None, &on(os)).unwrap();
+        (resolution.name, resolution.source)
+    };
+
+    assert_eq!(
+        resolve(&resolver, "windows"),
+        ("windows".to_string(), Source::Fallback)
+    );
+    assert_eq!(
+        resolve(&resolver, "macos"),
+        ("linux".to_string(), Source::Fallback)
+    );
+
+    resolver.set_fallback("html");
+    assert_eq!(resolver.fallback(&on("windows")), "html");
+    assert_eq!(
+        resolve(&resolver, "windows"),
+        ("html".to_string(), Source::Fallback)
+    );
+}
+
+#[test]
+fn registered_family_creates_its_products() {
+    let resolver = with_high_contrast();
+    let resolution = resolver
+        .resolve_with(
+            None,
+            Some("high-contrast"),
+            &capabilities(false, ColorSupport::None),
+        )
+        .unwrap();
+
+    let mut out = Vec::new();
+    create_gui(resolution.factory.as_ref(), &mut out).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "[[ OK ]]\nLinux Menu\nLinux Textbox\n"
+    );
+}
+
+#[test]
+fn unknown_names_are_errors() {
+    let mut resolver = with_high_contrast();
+    let terminal = capabilities(true, ColorSupport::Basic);
+
+    let err = resolver
+        .resolve_with(None, Some("motif"), &terminal)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        FamilyError::UnknownFamily {
+            name: "motif".to_string(),
+            source: Source::Environment,
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "no GUI family is registered under `motif` (from environment)"
+    );
+
+    resolver.unregister("ansi");
+    resolver.set_fallback("motif");
+    assert_eq!(
+        resolver.resolve_with(None, None, &terminal).unwrap_err(),
+        FamilyError::NoFamily
+    );
+}
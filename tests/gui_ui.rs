@@ -0,0 +1,238 @@
+// Headless tests of the widget tree: the UI is laid out and driven by synthetic events only.
+
+use std::cell::Cell;
+use std::io::{self, Write};
+use std::rc::Rc;
+
+use design_patterns::creational::abstract_factory::ansi::AnsiFactory;
+use design_patterns::creational::abstract_factory::ui::{Event, Key, Rect, Size, Stack, Ui};
+use design_patterns::creational::abstract_factory::{
+    Button, GUIFactory, LinuxFactory, LinuxMenu, LinuxTextbox, Menu, Textbox,
+};
+
+fn login_form() -> Ui {
+    let mut ui = Ui::new(Box::new(LinuxFactory {}));
+    let user = ui.add_textbox("user", "");
+    let password = ui.add_textbox("password", "");
+    let submit = ui.add_button("submit", "Submit");
+    let cancel = ui.add_button("cancel", "Cancel");
+    ui.set_fixed_size(user, Size::new(20, 1));
+    ui.set_fixed_size(password, Size::new(20, 1));
+    ui.set_root(
+        Stack::vertical()
+            .padding(1)
+            .spacing(1)
+            .child(user)
+            .child(password)
+            .child(Stack::horizontal().spacing(2).child(submit).child(cancel)),
+    );
+    ui
+}
+
+#[test]
+fn stacks_place_widgets_with_padding_and_spacing() {
+    let ui = login_form();
+    let id = |name| ui.find(name).unwrap();
+
+    assert_eq!(
+        ui.rect(id("user")),
+        Rect {
+            x: 1,
+            y: 1,
+            width: 20,
+            height: 1
+        }
+    );
+    assert_eq!(
+        ui.rect(id("password")),
+        Rect {
+            x: 1,
+            y: 3,
+            width: 20,
+            height: 1
+        }
+    );
+    // Buttons are measured from what they paint, "Linux Button"
+    assert_eq!(
+        ui.rect(id("submit")),
+        Rect {
+            x: 1,
+            y: 5,
+            width: 12,
+            height: 1
+        }
+    );
+    assert_eq!(
+        ui.rect(id("cancel")),
+        Rect {
+            x: 15,
+            y: 5,
+            width: 12,
+            height: 1
+        }
+    );
+    assert_eq!(ui.size(), Size::new(28, 7));
+}
+
+#[test]
+fn scripted_events_edit_the_focused_textbox() {
+    let mut ui = login_form();
+    let submitted = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&submitted);
+    let submit = ui.find("submit").unwrap();
+    ui.on_click(submit, move |_| counter.set(counter.get() + 1));
+
+    ui.run_script(
+        "# log in
+         click user
+         type alice
+         tab
+         type hunter3
+         backspace
+         key 2
+         tab
+         enter
+         click 2 5",
+    )
+    .unwrap();
+
+    let text = |name| ui.textbox(ui.find(name).unwrap()).unwrap().text();
+    assert_eq!(text("user"), "alice");
+    assert_eq!(text("password"), "hunter2");
+    assert_eq!(submitted.get(), 2);
+    assert_eq!(ui.focus(), Some(submit));
+}
+
+#[test]
+fn clicks_outside_widgets_clear_the_focus() {
+    let mut ui = login_form();
+    assert!(ui.dispatch(Event::Click { x: 1, y: 1 }));
+    assert_eq!(ui.focus(), ui.find("user"));
+
+    assert!(!ui.dispatch(Event::Click { x: 0, y: 0 }));
+    assert_eq!(ui.focus(), None);
+    assert!(!ui.dispatch(Event::Key(Key::Char('x'))));
+    assert_eq!(ui.textbox(ui.find("user").unwrap()).unwrap().text(), "");
+}
+
+#[test]
+fn backtab_wraps_around_and_menus_never_take_the_focus() {
+    let mut ui = Ui::new(Box::new(LinuxFactory {}));
+    let menu = ui.add_menu("menu");
+    let notes = ui.add_textbox("notes", "");
+    ui.set_root(Stack::vertical().child(menu).child(notes));
+
+    ui.dispatch(Event::Key(Key::BackTab));
+    assert_eq!(ui.focus(), Some(notes));
+    ui.dispatch(Event::Key(Key::Tab));
+    assert_eq!(ui.focus(), Some(notes));
+    ui.dispatch(Event::Click { x: 0, y: 0 });
+    assert_eq!(ui.focus(), None);
+}
+
+#[test]
+fn script_errors_point_to_the_line() {
+    let mut ui = login_form();
+    let err = ui.run_script("tab\n\nclick nowhere").unwrap_err();
+    assert_eq!(err.line, 3);
+    assert_eq!(err.to_string(), "line 3: no widget called `nowhere`");
+
+    let err = ui.run_script("jump").unwrap_err();
+    assert_eq!(err.to_string(), "line 1: unknown command `jump`");
+}
+
+#[test]
+fn canvas_grows_with_the_text_of_an_ansi_textbox() {
+    let mut ui = Ui::new(Box::new(AnsiFactory::default()));
+    let notes = ui.add_textbox("notes", "ab");
+    let ok = ui.add_button("ok", "OK");
+    ui.set_root(Stack::horizontal().spacing(1).child(notes).child(ok));
+    let before = ui.rect(notes).width;
+
+    ui.run_script("click notes\ntype cdef").unwrap();
+
+    assert_eq!(ui.rect(notes).width, before + 4);
+    assert_eq!(ui.rect(ok).x, ui.rect(notes).width + 1);
+    assert!(ui.render_canvas().contains("abcdef"));
+}
+
+#[test]
+fn huge_widgets_saturate_instead_of_overflowing() {
+    let mut ui = Ui::new(Box::new(AnsiFactory::default()));
+    let notes = ui.add_textbox("notes", &"x".repeat(70_000));
+    let log = ui.add_textbox("log", &"y".repeat(70_000));
+    let ok = ui.add_button("ok", "OK");
+    ui.set_root(
+        Stack::vertical()
+            .padding(1)
+            .child(notes)
+            .child(Stack::horizontal().spacing(1).child(log).child(ok)),
+    );
+
+    assert_eq!(ui.size().width, u16::MAX);
+    assert_eq!(ui.rect(notes).width, u16::MAX);
+    assert_eq!(ui.rect(ok).x, u16::MAX);
+    assert_eq!(ui.widget_at(u16::MAX - 1, 1), Some(notes));
+    ui.run_script("click notes\nkey z").unwrap();
+    assert_eq!(ui.focus(), Some(notes));
+}
+
+// Linux widgets whose buttons count how often they are painted
+struct CountingFactory {
+    paints: Rc<Cell<usize>>,
+}
+
+struct CountingButton {
+    paints: Rc<Cell<usize>>,
+}
+
+impl Button for CountingButton {
+    fn paint(&self, out: &mut dyn Write) -> io::Result<()> {
+        self.paints.set(self.paints.get() + 1);
+        writeln!(out, "[OK]")
+    }
+}
+
+impl GUIFactory for CountingFactory {
+    fn create_button(&self) -> Box<dyn Button> {
+        Box::new(CountingButton {
+            paints: Rc::clone(&self.paints),
+        })
+    }
+
+    fn create_menu(&self) -> Box<dyn Menu> {
+        Box::new(LinuxMenu {})
+    }
+
+    fn create_textbox(&self) -> Box<dyn Textbox> {
+        Box::new(LinuxTextbox::default())
+    }
+}
+
+#[test]
+fn a_layout_pass_paints_every_widget_once() {
+    let paints = Rc::new(Cell::new(0));
+    let mut ui = Ui::new(Box::new(CountingFactory {
+        paints: Rc::clone(&paints),
+    }));
+    let ok = ui.add_button("ok", "OK");
+    let cancel = ui.add_button("cancel", "Cancel");
+    let mut root = Stack::horizontal().child(ok).child(cancel);
+    for _ in 0..8 {
+        root = Stack::vertical().padding(1).child(root);
+    }
+
+    ui.set_root(root);
+
+    assert_eq!(paints.get(), 2);
+    assert_eq!(
+        ui.rect(ok),
+        Rect {
+            x: 8,
+            y: 8,
+            width: 4,
+            height: 1
+        }
+    );
+    assert_eq!(ui.rect(cancel).x, 12);
+}
@@ -0,0 +1,223 @@
+use design_patterns::creational::builder::layered::{ConfigError, Layer, LayeredClusterBuilder};
+use design_patterns::creational::builder::{NodePoolBuilder, Violation};
+
+fn layers(builder: &LayeredClusterBuilder) -> Vec<(&str, Layer, String)> {
+    builder
+        .sources()
+        .into_iter()
+        .map(|setting| (setting.field, setting.layer, setting.origin.clone()))
+        .collect()
+}
+
+#[test]
+fn higher_layers_win_whatever_the_call_order() {
+    let mut builder = LayeredClusterBuilder::new();
+    // Explicit first, file last, the explicit call still wins
+    builder.name("explicit".to_string());
+    builder
+        .env_vars([
+            ("DESIGN_PATTERNS_CLUSTER_VERSION", "1.30"),
+            ("PATH", "/usr/bin"),
+        ])
+        .unwrap();
+    builder
+        .file_contents(
+            "cluster.conf",
+            "# defaults for prod\nname = from-file\n\nversion = 1.28\nauto_upgrade = yes\n",
+        )
+        .unwrap();
+
+    assert_eq!(
+        layers(&builder),
+        [
+            ("name", Layer::Explicit, "name()".to_string()),
+            (
+                "version",
+                Layer::Env,
+                "DESIGN_PATTERNS_CLUSTER_VERSION".to_string()
+            ),
+            ("auto_upgrade", Layer::File, "cluster.conf:5".to_string()),
+        ]
+    );
+
+    let cluster = builder.build().unwrap();
+    assert_eq!(cluster.name(), "explicit");
+    assert_eq!(cluster.version(), "1.30");
+    assert!(cluster.auto_upgrade());
+}
+
+#[test]
+fn defaults_apply_until_a_layer_overrides_them() {
+    let mut builder = LayeredClusterBuilder::new();
+    builder.name("prod".to_string());
+    assert_eq!(builder.source("version").unwrap().layer, Layer::Default);
+    assert_eq!(
+        builder.source("auto_upgrade").unwrap().to_string(),
+        "auto_upgrade = false (default, built-in)"
+    );
+
+    let cluster = builder.build().unwrap();
+    assert_eq!(cluster.version(), "1.31");
+    assert!(!cluster.auto_upgrade());
+
+    // Later files override earlier ones within the same layer
+    builder
+        .file_contents("base.conf", "version = 1.28")
+        .unwrap()
+        .file_contents("prod.conf", "version = 1.29")
+        .unwrap();
+    assert_eq!(builder.source("version").unwrap().origin, "prod.conf:1");
+}
+
+#[test]
+fn reads_config_files_from_disk() {
+    let path = std::env::temp_dir().join(format!("layered-{}.conf", std::process::id()));
+    std::fs::write(&path, "name = disk\nversion = 1.29\n").unwrap();
+    let mut builder = LayeredClusterBuilder::new();
+    let result = builder.file(&path).map(|builder| builder.build());
+    std::fs::remove_file(&path).unwrap();
+
+    let cluster = result.unwrap().unwrap();
+    assert_eq!(cluster.name(), "disk");
+    assert_eq!(
+        builder.source("name").unwrap().origin,
+        format!("{}:1", path.display())
+    );
+
+    let missing = builder.file(path).err().unwrap();
+    assert!(matches!(missing, ConfigError::Io { .. }));
+}
+
+#[test]
+fn bad_sources_are_reported_with_their_origin() {
+    let mut builder = LayeredClusterBuilder::new();
+
+    let err = builder
+        .file_contents("cluster.conf", "name = prod\nversion 1.30\n")
+        .err()
+        .unwrap();
+    assert!(matches!(err, ConfigError::Syntax { .. }));
+    assert_eq!(
+        err.to_string(),
+        "cluster.conf:2: expected `key = value`, got `version 1.30`"
+    );
+
+    let err = builder
+        .file_contents("cluster.conf", "zone = europe-west1")
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "cluster.conf:1: unknown key `zone`");
+
+    let err = builder
+        .env_vars([("DESIGN_PATTERNS_CLUSTER_AUTO_UPGRADE", "maybe")])
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "DESIGN_PATTERNS_CLUSTER_AUTO_UPGRADE: `maybe` is not a valid auto_upgrade"
+    );
+}
+
+#[test]
+fn failed_sources_leave_the_builder_unchanged() {
+    let mut builder = LayeredClusterBuilder::new();
+    builder.name("prod".to_string());
+    let describe = |builder: &LayeredClusterBuilder| -> Vec<String> {
+        builder.sources().iter().map(ToString::to_string).collect()
+    };
+    let before = describe(&builder);
+
+    // The lines before the bad one are dropped too
+    builder
+        .file_contents(
+            "cluster.conf",
+            "version = 1.29
+auto_upgrade = sometimes
+",
+        )
+        .err()
+        .unwrap();
+    builder
+        .env_vars([
+            ("DESIGN_PATTERNS_CLUSTER_VERSION", "1.30"),
+            ("DESIGN_PATTERNS_CLUSTER_ZONE", "europe-west1"),
+        ])
+        .err()
+        .unwrap();
+
+    assert_eq!(describe(&builder), before);
+    assert_eq!(builder.source("version").unwrap().layer, Layer::Default);
+}
+
+#[cfg(unix)]
+#[test]
+fn only_prefixed_variables_have_to_be_unicode() {
+    use std::ffi::OsString;
+    use std::os::unix::ffi::OsStringExt;
+
+    let invalid = || OsString::from_vec(b"caf\xe9".to_vec());
+    let var = |key: &str, value: OsString| (OsString::from(key), value);
+    let mut builder = LayeredClusterBuilder::new();
+
+    builder
+        .env_vars([
+            var("LANG_SAMPLE", invalid()),
+            (invalid(), OsString::from("1")),
+            var("DESIGN_PATTERNS_CLUSTER_NAME", OsString::from("prod")),
+        ])
+        .unwrap();
+    assert_eq!(builder.source("name").unwrap().value, "prod");
+
+    let err = builder
+        .env_vars([
+            var("DESIGN_PATTERNS_CLUSTER_VERSION", OsString::from("1.30")),
+            var("DESIGN_PATTERNS_CLUSTER_NAME", invalid()),
+        ])
+        .err()
+        .unwrap();
+    assert!(matches!(err, ConfigError::NotUnicode { .. }));
+    assert_eq!(
+        err.to_string(),
+        "DESIGN_PATTERNS_CLUSTER_NAME: not valid Unicode"
+    );
+
+    let mut key = b"DESIGN_PATTERNS_CLUSTER_".to_vec();
+    key.extend(b"\xff");
+    let err = builder
+        .env_vars([(OsString::from_vec(key), OsString::from("prod"))])
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "DESIGN_PATTERNS_CLUSTER_\u{fffd}: not valid Unicode"
+    );
+    assert_eq!(builder.source("version").unwrap().layer, Layer::Default);
+}
+
+#[test]
+fn layered_values_are_validated_by_the_builder() {
+    let mut builder = LayeredClusterBuilder::new();
+    builder
+        .env_vars([("DESIGN_PATTERNS_CLUSTER_VERSION", "1.20")])
+        .unwrap()
+        .node_pool(&mut NodePoolBuilder::new(
+            "system".to_string(),
+            "e2-small".to_string(),
+        ));
+
+    // No layer sets a name
+    let err = builder.build().unwrap_err();
+    assert!(matches!(
+        err.violations(),
+        [
+            Violation::InvalidName { .. },
+            Violation::UnsupportedVersion(_)
+        ]
+    ));
+
+    let cluster = builder
+        .name("prod".to_string())
+        .version("1.30".to_string())
+        .build();
+    assert_eq!(cluster.unwrap().node_pools().len(), 1);
+}
@@ -0,0 +1,230 @@
+use design_patterns::creational::builder::manifest::{self, Format, Location, ManifestError};
+use design_patterns::creational::builder::{
+    KubernetesCluster, KubernetesClusterBuilder, NodePoolBuilder, Taint, TaintEffect, Violation,
+};
+
+fn production() -> KubernetesCluster {
+    KubernetesClusterBuilder::new("prod".to_string(), "1.30".to_string())
+        .auto_upgrade(true)
+        .node_pool(
+            NodePoolBuilder::new("system".to_string(), "e2-standard-4".to_string())
+                .autoscaling(1, 3)
+                .taint(Taint::new(
+                    "CriticalAddonsOnly",
+                    "true",
+                    TaintEffect::NoSchedule,
+                )),
+        )
+        .node_pool(
+            NodePoolBuilder::new("spot".to_string(), "c2-standard-16".to_string())
+                .autoscaling(0, 50)
+                .spot(true)
+                .label("workload", "batch")
+                .zone("europe-west1-b")
+                .zone("europe-west1-c"),
+        )
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn yaml_manifest_is_readable_and_round_trips() {
+    let cluster = production();
+    let yaml = manifest::to_yaml(&cluster);
+
+    assert_eq!(
+        yaml,
+        "\
+name: prod
+version: '1.30'
+auto_upgrade: true
+node_pools:
+- name: system
+  machine_type: e2-standard-4
+  min_nodes: 1
+  max_nodes: 3
+  taints:
+  - key: CriticalAddonsOnly
+    value: 'true'
+    effect: NoSchedule
+- name: spot
+  machine_type: c2-standard-16
+  min_nodes: 0
+  max_nodes: 50
+  spot: true
+  labels:
+    workload: batch
+  zones:
+  - europe-west1-b
+  - europe-west1-c
+"
+    );
+    assert_eq!(manifest::from_yaml(&yaml).unwrap(), cluster);
+}
+
+#[test]
+fn json_manifest_round_trips() {
+    let cluster = production();
+    let json = manifest::to_manifest(&cluster, Format::Json);
+    let loaded = manifest::from_manifest(&json, Format::Json).unwrap();
+
+    assert_eq!(loaded, cluster);
+    assert_eq!(manifest::to_json(&loaded), json);
+    // Both formats describe the same spec
+    assert_eq!(
+        manifest::from_yaml(&manifest::to_yaml(&loaded)).unwrap(),
+        cluster
+    );
+}
+
+#[test]
+fn left_out_fields_get_the_builder_defaults() {
+    let cluster = manifest::from_yaml(
+        "name: dev
+version: '1.28.3'
+node_pools:
+- name: default
+  machine_type: e2-small
+",
+    )
+    .unwrap();
+
+    assert!(!cluster.auto_upgrade());
+    let pool = cluster.node_pool("default").unwrap();
+    assert_eq!(
+        (pool.min_nodes(), pool.max_nodes(), pool.spot()),
+        (1, 3, false)
+    );
+}
+
+#[test]
+fn spec_violations_point_to_their_lines() {
+    let err = manifest::from_yaml(
+        "name: prod
+version: '1.30.2'
+auto_upgrade: true
+node_pools:
+- name: compute
+  machine_type: e2-standard-4
+  min_nodes: 4
+  max_nodes: 2
+- name: compute
+  machine_type: e2-standard-4
+",
+    )
+    .unwrap_err();
+
+    let ManifestError::Invalid { error, locations } = &err else {
+        panic!("expected spec violations, got {err}");
+    };
+    let fields: Vec<&str> = error.violations().iter().map(Violation::field).collect();
+    assert_eq!(
+        fields,
+        [
+            "auto_upgrade",
+            "node_pools[0].max_nodes",
+            "node_pools[1].name"
+        ]
+    );
+    let lines: Vec<usize> = locations
+        .iter()
+        .map(|location| location.unwrap().line)
+        .collect();
+    assert_eq!(lines, [3, 8, 9]);
+    assert_eq!(
+        err.to_string().lines().nth(3),
+        Some("  line 9: node_pools[1].name: `compute` is already the name of node_pools[0]")
+    );
+}
+
+#[test]
+fn json_violations_point_to_their_lines() {
+    let err = manifest::from_json(
+        r#"{
+  "name": "Prod",
+  "version": "1.30",
+  "node_pools": [
+    {
+      "name": "gpu",
+      "machine_type": "a2-highgpu-1g",
+      "zones": ["us-central1-a", "us-central1-a"]
+    }
+  ]
+}"#,
+    )
+    .unwrap_err();
+
+    let ManifestError::Invalid { locations, .. } = err else {
+        panic!("expected spec violations");
+    };
+    let lines: Vec<usize> = locations
+        .iter()
+        .map(|location| location.unwrap().line)
+        .collect();
+    assert_eq!(lines, [2, 8]);
+}
+
+#[test]
+fn parse_errors_point_to_line_and_field() {
+    let err = manifest::from_yaml(
+        "name: prod
+version: '1.30'
+node_pools:
+- name: compute
+  machine_type: e2-standard-4
+  min_nodes: lots
+",
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ManifestError::Parse {
+            format: Format::Yaml,
+            field: "node_pools[0].min_nodes".to_string(),
+            location: Some(Location {
+                line: 6,
+                column: 14
+            }),
+            message: "invalid type: string \"lots\", expected u32".to_string(),
+        }
+    );
+
+    let err = manifest::from_json(
+        "{\n  \"name\": \"prod\",\n  \"version\": \"1.30\",\n  \"autoupgrade\": true\n}",
+    )
+    .unwrap_err();
+    let ManifestError::Parse {
+        field, location, ..
+    } = &err
+    else {
+        panic!("expected a parse error");
+    };
+    assert_eq!((field.as_str(), location.unwrap().line), ("autoupgrade", 4));
+    assert!(err.to_string().starts_with(
+        "invalid JSON manifest at line 4, column 15, field autoupgrade: unknown field `autoupgrade`"
+    ));
+}
+
+#[test]
+fn unquoted_versions_are_rejected() {
+    // To YAML this is the number 1.3, not the version 1.30
+    let err = manifest::from_yaml("name: prod\nversion: 1.30\n").unwrap_err();
+    let ManifestError::Parse {
+        field, location, ..
+    } = &err
+    else {
+        panic!("expected a parse error");
+    };
+    assert_eq!((field.as_str(), location.unwrap().line), ("version", 2));
+    assert!(err.to_string().ends_with(
+        "invalid type: floating point `1.3`, expected a version in quotes, e.g. '1.30'"
+    ));
+
+    let err = manifest::from_json("{\"name\": \"prod\", \"version\": 1}").unwrap_err();
+    assert!(err.to_string().ends_with(
+        "field version: invalid type: integer `1`, expected a version in quotes, e.g. '1.30'"
+    ));
+
+    let cluster = manifest::from_yaml("name: prod\nversion: \"1.30\"\n").unwrap();
+    assert_eq!(cluster.version().to_string(), "1.30");
+}
@@ -0,0 +1,224 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Barrier, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use design_patterns::creational::singleton::Multiton;
+
+// Records its name in the log when it is dropped
+struct Tracked {
+    name: String,
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.log.lock().unwrap().push(self.name.clone());
+    }
+}
+
+#[test]
+fn one_instance_per_key_under_contention() {
+    const THREADS: usize = 24;
+    static CONSTRUCTIONS: AtomicUsize = AtomicUsize::new(0);
+    static POOLS: Multiton<String, String> = Multiton::new(|url| {
+        CONSTRUCTIONS.fetch_add(1, Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(10));
+        Ok(format!("pool for {url}"))
+    });
+
+    let barrier = Arc::new(Barrier::new(THREADS));
+    let handles: Vec<_> = (0..THREADS)
+        .map(|i| {
+            let barrier = Arc::clone(&barrier);
+            thread::spawn(move || {
+                barrier.wait();
+                let url = ["db-a", "db-b", "db-c"][i % 3];
+                (url, POOLS.instance(url))
+            })
+        })
+        .collect();
+    let pools: Vec<_> = handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect();
+
+    assert_eq!(CONSTRUCTIONS.load(Ordering::SeqCst), 3);
+    for (url, pool) in &pools {
+        assert!(Arc::ptr_eq(pool, &POOLS.try_get(*url).unwrap()));
+        assert_eq!(**pool, format!("pool for {url}"));
+    }
+    let mut keys = POOLS.keys();
+    keys.sort();
+    assert_eq!(keys, ["db-a", "db-b", "db-c"]);
+}
+
+#[test]
+fn slow_keys_do_not_block_other_keys() {
+    let (started, slow_started) = mpsc::channel();
+    let (release, released) = mpsc::channel::<()>();
+    let released = Mutex::new(released);
+    let multiton = Multiton::new(|key: &String| {
+        if key == "slow" {
+            started.send(()).unwrap();
+            // Only returns once "fast" has been constructed
+            released
+                .lock()
+                .unwrap()
+                .recv_timeout(Duration::from_secs(5))
+                .map_err(|_| "fast was blocked by slow".to_string())?;
+        }
+        Ok::<_, String>(key.clone())
+    });
+
+    thread::scope(|scope| {
+        let slow = scope.spawn(|| multiton.get("slow"));
+        slow_started.recv().unwrap();
+        assert_eq!(*multiton.get("fast").unwrap(), "fast");
+        assert!(!multiton.contains("slow"));
+        release.send(()).unwrap();
+        assert_eq!(*slow.join().unwrap().unwrap(), "slow");
+    });
+    assert_eq!(multiton.keys(), ["fast", "slow"]);
+}
+
+#[test]
+fn failed_keys_are_retried_and_not_listed() {
+    let attempts = AtomicUsize::new(0);
+    let tenants = Multiton::new(|tenant: &String| {
+        if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+            Err(format!("{tenant} is unreachable"))
+        } else {
+            Ok(tenant.to_uppercase())
+        }
+    });
+
+    assert_eq!(tenants.get("acme").unwrap_err(), "acme is unreachable");
+    assert!(!tenants.contains("acme"));
+    assert!(tenants.is_empty());
+
+    assert_eq!(*tenants.get("acme").unwrap(), "ACME");
+    assert_eq!(tenants.len(), 1);
+}
+
+#[test]
+fn teardown_drops_newest_first() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let multiton = Multiton::new(|name: &String| {
+        Ok::<_, String>(Tracked {
+            name: name.clone(),
+            log: Arc::clone(&log),
+        })
+    });
+    for name in ["b-first", "a-second", "c-third"] {
+        multiton.get(name).unwrap();
+    }
+    assert_eq!(multiton.keys(), ["b-first", "a-second", "c-third"]);
+
+    // A handle that is still held keeps its instance alive past the teardown
+    let held = multiton.get("a-second").unwrap();
+    assert_eq!(multiton.teardown(), ["c-third", "a-second", "b-first"]);
+    assert_eq!(*log.lock().unwrap(), ["c-third", "b-first"]);
+    assert!(multiton.is_empty());
+
+    drop(held);
+    assert_eq!(*log.lock().unwrap(), ["c-third", "b-first", "a-second"]);
+
+    // Torn down keys are constructed again on the next get()
+    multiton.get("b-first").unwrap();
+    assert_eq!(multiton.keys(), ["b-first"]);
+}
+
+#[test]
+fn remove_and_drop_tear_down_too() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let multiton = Multiton::new(|name: &String| {
+        Ok::<_, String>(Tracked {
+            name: name.clone(),
+            log: Arc::clone(&log),
+        })
+    });
+    for name in ["one", "two", "three", "four"] {
+        multiton.get(name).unwrap();
+    }
+
+    drop(multiton.remove("two"));
+    assert!(multiton.remove("two").is_none());
+    assert_eq!(*log.lock().unwrap(), ["two"]);
+
+    drop(multiton);
+    assert_eq!(*log.lock().unwrap(), ["two", "four", "three", "one"]);
+}
+
+#[test]
+fn get_racing_remove_never_leaves_an_orphan() {
+    const THREADS: usize = 8;
+    const ROUNDS: usize = 2_000;
+    let constructed = AtomicUsize::new(0);
+    let multiton =
+        Multiton::new(|_: &String| Ok::<_, String>(constructed.fetch_add(1, Ordering::SeqCst)));
+
+    // Every instance must come back out of remove() or teardown() exactly once
+    let released = Mutex::new(Vec::new());
+    thread::scope(|scope| {
+        for _ in 0..THREADS {
+            scope.spawn(|| {
+                for _ in 0..ROUNDS {
+                    multiton.get("key").unwrap();
+                    if let Some(instance) = multiton.remove("key") {
+                        released.lock().unwrap().push(*instance);
+                    }
+                }
+            });
+        }
+    });
+    let mut released = released.into_inner().unwrap();
+    released.extend(
+        multiton
+            .instances()
+            .into_iter()
+            .map(|(_, instance)| *instance),
+    );
+    multiton.teardown();
+
+    released.sort_unstable();
+    let all: Vec<usize> = (0..constructed.load(Ordering::SeqCst)).collect();
+    assert_eq!(released, all);
+}
+
+#[test]
+fn callers_waiting_for_a_failed_key_construct_it_again() {
+    let attempts = AtomicUsize::new(0);
+    let multiton = Multiton::new(|key: &String| {
+        thread::sleep(Duration::from_millis(10));
+        if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+            Err(format!("{key} is unreachable"))
+        } else {
+            Ok(key.to_uppercase())
+        }
+    });
+
+    // Whichever caller runs the initializer first fails, the other one constructs the key again
+    let barrier = Barrier::new(2);
+    let (mut failed, mut succeeded): (Vec<_>, Vec<_>) = thread::scope(|scope| {
+        let callers: Vec<_> = (0..2)
+            .map(|_| {
+                scope.spawn(|| {
+                    barrier.wait();
+                    multiton.get("acme")
+                })
+            })
+            .collect();
+        callers
+            .into_iter()
+            .map(|caller| caller.join().unwrap())
+            .partition(Result::is_err)
+    });
+
+    assert_eq!(failed.pop().unwrap().unwrap_err(), "acme is unreachable");
+    let instance = succeeded.pop().unwrap().unwrap();
+    assert_eq!(*instance, "ACME");
+    assert!(Arc::ptr_eq(&multiton.try_get("acme").unwrap(), &instance));
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,104 @@
+use design_patterns::behavioral::chain_of_responsibility::{
+    DeliveryA, Department, Order, PaymentA, User,
+};
+use design_patterns::behavioral::command::{TVOnCommand, TVRemoteControl, TV};
+use design_patterns::behavioral::observer::{YoutubeChannel, YoutubeUser};
+use design_patterns::creational::abstract_factory::{create_gui, LinuxFactory};
+use design_patterns::creational::factory::{AnimalFactory, AnimalType};
+use design_patterns::structural::composite::{Component, File, Folder};
+use design_patterns::structural::facade::{Delivery, Operation, Payment, PlaceOrder};
+
+fn capture(f: impl FnOnce(&mut Vec<u8>) -> std::io::Result<()>) -> String {
+    let mut out = Vec::new();
+    f(&mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn animal_speaks_into_writer() {
+    let dog = AnimalFactory::create_animal(AnimalType::Dog);
+    assert_eq!(capture(|out| dog.speak(out)), "Dog says: Woof!\n");
+}
+
+#[test]
+fn gui_paints_into_writer() {
+    assert_eq!(
+        capture(|out| create_gui(&LinuxFactory {}, out)),
+        "Linux Button\nLinux Menu\nLinux Textbox\n"
+    );
+}
+
+#[test]
+fn composite_search_visits_every_component() {
+    let mut folder = Folder::new("docs");
+    folder.insert_component(File::new("readme"));
+
+    assert_eq!(
+        capture(|out| folder.search("rose", out)),
+        "Searching recursively for keyword rose in folder docs\nSearching for rose in file readme\n"
+    );
+}
+
+#[test]
+fn facade_completes_order_into_writer() {
+    let operation = Operation {
+        order: PlaceOrder,
+        payment: Payment,
+        delivery: Delivery,
+    };
+
+    assert_eq!(
+        capture(|out| operation.complete_order(out)),
+        "Order placed\nPayment received\nOrder Delivered\n"
+    );
+}
+
+#[test]
+fn channel_notifies_every_subscriber() {
+    let mut channel = YoutubeChannel::new("funtime".to_string());
+    channel.subscribe(Box::new(YoutubeUser {
+        name: "sub1".to_string(),
+    }));
+    channel.subscribe(Box::new(YoutubeUser {
+        name: "sub2".to_string(),
+    }));
+
+    assert_eq!(
+        capture(|out| channel.notify("New video".to_string(), out)),
+        "User sub1 received notification: New video\nUser sub2 received notification: New video\n"
+    );
+}
+
+#[test]
+fn remote_control_executes_commands() {
+    let mut remote_control = TVRemoteControl::new();
+    remote_control.set_command(1, Box::new(TVOnCommand::new(TV::new())));
+
+    assert_eq!(
+        capture(|out| remote_control.press_button(1, out)),
+        "TV is on, watch movies.\n"
+    );
+    assert_eq!(
+        capture(|out| remote_control.press_button(2, out)),
+        "do nothing.\n"
+    );
+}
+
+#[test]
+fn chain_handles_request_once() {
+    let mut user = User::default();
+    let mut order = Order {
+        next: Some(Box::new(PaymentA {
+            next: Some(Box::new(DeliveryA { next: None })),
+        })),
+    };
+
+    assert_eq!(
+        capture(|out| order.execute(&mut user, out)),
+        "Order placed\nPayment done\nDelivered\n"
+    );
+    assert_eq!(
+        capture(|out| order.execute(&mut user, out)),
+        "Order is already placed\nPayment is already done\nDelivery is already done\n"
+    );
+}
@@ -0,0 +1,138 @@
+use std::sync::Arc;
+use std::thread;
+
+use design_patterns::creational::prototype::registry::PrototypeRegistryError;
+use design_patterns::creational::prototype::{Human, Prototype, PrototypeRegistry};
+
+fn human(name: &str, age: i32) -> Human {
+    Human {
+        name: name.to_string(),
+        age,
+    }
+}
+
+#[test]
+fn clones_a_mixed_collection_through_the_trait_object() {
+    let originals: Vec<Box<dyn Prototype>> = vec![
+        Box::new(human("Chethan", 21)),
+        Box::new(vec![1, 2, 3]),
+        Box::new(42_u8),
+    ];
+    let mut clones: Vec<Box<dyn Prototype>> = originals
+        .iter()
+        .map(|original| original.as_ref().get_clone())
+        .collect();
+
+    // The clones are independent of the originals
+    clones[0].downcast_mut::<Human>().unwrap().age = 30;
+    clones[1].downcast_mut::<Vec<i32>>().unwrap().push(4);
+
+    assert_eq!(originals[0].downcast_ref(), Some(&human("Chethan", 21)));
+    assert_eq!(clones[0].downcast_ref(), Some(&human("Chethan", 30)));
+    assert_eq!(originals[1].downcast_ref(), Some(&vec![1, 2, 3]));
+    assert_eq!(clones[1].downcast_ref(), Some(&vec![1, 2, 3, 4]));
+    assert_eq!(clones[2].downcast_ref(), Some(&42_u8));
+}
+
+#[test]
+fn clone_keeps_its_concrete_type() {
+    let human = human("Chethan", 21);
+    let clone = human.get_clone();
+
+    assert!(clone.is::<Human>());
+    assert!(!clone.is::<String>());
+    assert_eq!(*clone.downcast::<Human>().unwrap(), human);
+}
+
+#[test]
+fn failed_downcast_returns_the_box() {
+    let clone = human("Chethan", 21).get_clone();
+
+    let clone = clone.downcast::<String>().unwrap_err();
+    assert_eq!(clone.downcast_ref::<Human>().unwrap().name, "Chethan");
+}
+
+#[test]
+fn registry_clones_templates_with_overrides() {
+    let registry = PrototypeRegistry::new();
+    assert_eq!(registry.names(), ["default-admin", "default-user"]);
+
+    let alice: Human = registry
+        .create_with("default-admin", |admin: &mut Human| {
+            admin.name = "Alice".to_string()
+        })
+        .unwrap();
+    assert_eq!(alice, human("Alice", 30));
+    // The template itself is untouched
+    assert_eq!(
+        registry.create::<Human>("default-admin").unwrap(),
+        human("admin", 30)
+    );
+
+    registry.register("answer", 42_u32);
+    assert_eq!(registry.create::<u32>("answer").unwrap(), 42);
+    assert_eq!(
+        registry.create::<Human>("answer").unwrap_err(),
+        PrototypeRegistryError::WrongType {
+            name: "answer".to_string(),
+            expected: std::any::type_name::<Human>(),
+        }
+    );
+    assert_eq!(
+        registry.create::<Human>("root").unwrap_err().to_string(),
+        "no template is registered under `root`"
+    );
+}
+
+#[test]
+fn registry_templates_are_replaced_at_runtime() {
+    let registry = PrototypeRegistry::empty();
+    assert!(registry.replace("guest", human("guest", 0)).is_err());
+
+    registry.register("guest", human("guest", 0));
+    let old = registry.replace("guest", human("visitor", 99)).unwrap();
+    assert_eq!(old.as_ref().downcast_ref(), Some(&human("guest", 0)));
+    assert_eq!(
+        registry.create::<Human>("guest").unwrap(),
+        human("visitor", 99)
+    );
+
+    assert!(registry.unregister("guest").is_some());
+    assert!(!registry.is_registered("guest"));
+}
+
+#[test]
+fn registry_is_shared_between_threads() {
+    let registry = Arc::new(PrototypeRegistry::new());
+    let readers: Vec<_> = (0..8)
+        .map(|i| {
+            let registry = Arc::clone(&registry);
+            thread::spawn(move || {
+                (0..100)
+                    .map(|_| {
+                        registry
+                            .create_with("default-user", |user: &mut Human| user.age += i)
+                            .unwrap()
+                    })
+                    .collect::<Vec<Human>>()
+            })
+        })
+        .collect();
+    let writer = {
+        let registry = Arc::clone(&registry);
+        thread::spawn(move || {
+            for age in 0..100 {
+                registry.register(format!("fixture-{age}"), human("fixture", age));
+            }
+        })
+    };
+
+    writer.join().unwrap();
+    for (i, reader) in readers.into_iter().enumerate() {
+        let users = reader.join().unwrap();
+        assert!(users
+            .iter()
+            .all(|user| *user == human("user", 18 + i as i32)));
+    }
+    assert_eq!(registry.names().len(), 102);
+}
@@ -0,0 +1,119 @@
+use std::io::{self, Write};
+
+use design_patterns::creational::factory::serialization::{self, SerializationError};
+use design_patterns::creational::factory::{Animal, AnimalFactory, AnimalType};
+
+struct Cow;
+
+impl Animal for Cow {
+    fn speak(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Cow says: Moo!")
+    }
+
+    fn kind(&self) -> &str {
+        "cow"
+    }
+}
+
+fn factory() -> AnimalFactory {
+    let mut factory = AnimalFactory::new();
+    factory.register("cow", || Box::new(Cow));
+    factory
+}
+
+fn animals() -> Vec<Box<dyn Animal>> {
+    vec![
+        AnimalFactory::create_animal(AnimalType::Dog),
+        Box::new(Cow),
+        AnimalFactory::create_animal(AnimalType::Cat),
+    ]
+}
+
+fn kinds(animals: &[Box<dyn Animal>]) -> Vec<&str> {
+    animals.iter().map(|animal| animal.kind()).collect()
+}
+
+#[test]
+fn json_round_trip() {
+    let json = serialization::to_json(&factory(), &animals()).unwrap();
+    assert_eq!(json, r#"[{"kind":"dog"},{"kind":"cow"},{"kind":"cat"}]"#);
+
+    let animals = serialization::from_json(&factory(), &json).unwrap();
+    assert_eq!(kinds(&animals), ["dog", "cow", "cat"]);
+}
+
+#[test]
+fn binary_round_trip() {
+    let bytes = serialization::to_bytes(&factory(), &animals()).unwrap();
+    assert_eq!(&bytes[..6], b"ANML\x01\x03");
+
+    let animals = serialization::from_bytes(&factory(), &bytes).unwrap();
+    assert_eq!(kinds(&animals), ["dog", "cow", "cat"]);
+}
+
+#[test]
+fn unknown_kinds_are_errors_when_reading() {
+    let json = serialization::to_json(&factory(), &animals()).unwrap();
+    let err = serialization::from_json(&AnimalFactory::new(), &json).err();
+    assert!(matches!(
+        err,
+        Some(SerializationError::UnknownKind { index: 1, ref kind }) if kind == "cow"
+    ));
+
+    let bytes = serialization::to_bytes(&factory(), &animals()).unwrap();
+    let err = serialization::from_bytes(&AnimalFactory::new(), &bytes).err();
+    assert!(matches!(
+        err,
+        Some(SerializationError::UnknownKind { index: 1, .. })
+    ));
+}
+
+// Neither registered nor given a kind(), the default is its type name
+struct Parrot;
+
+impl Animal for Parrot {
+    fn speak(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Parrot says: Hello!")
+    }
+}
+
+#[test]
+fn unknown_kinds_are_errors_when_writing() {
+    let animals: Vec<Box<dyn Animal>> = vec![Box::new(Cow), Box::new(Parrot)];
+
+    let err = serialization::to_json(&factory(), &animals).unwrap_err();
+    assert!(matches!(
+        err,
+        SerializationError::UnknownKind { index: 1, ref kind } if kind.ends_with("::Parrot")
+    ));
+    assert!(matches!(
+        serialization::to_bytes(&factory(), &animals),
+        Err(SerializationError::UnknownKind { index: 1, .. })
+    ));
+    // Kinds are checked against the factory that is passed, which may not know cow
+    assert!(matches!(
+        serialization::to_json(&AnimalFactory::new(), &animals[..1]),
+        Err(SerializationError::UnknownKind { index: 0, .. })
+    ));
+}
+
+#[test]
+fn malformed_input_is_rejected() {
+    let factory = factory();
+
+    assert!(matches!(
+        serialization::from_json(&factory, r#"[{"kind":"dog","age":3}]"#),
+        Err(SerializationError::Json(_))
+    ));
+
+    let mut bytes = serialization::to_bytes(&factory, &animals()).unwrap();
+    bytes.pop();
+    assert!(matches!(
+        serialization::from_bytes(&factory, &bytes),
+        Err(SerializationError::InvalidBinary(_))
+    ));
+    assert!(matches!(
+        serialization::from_bytes(&factory, b"ANML\x01\xff\xff\xff\xff\x0f"),
+        Err(SerializationError::InvalidBinary(_))
+    ));
+}
@@ -0,0 +1,96 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Barrier};
+use std::thread;
+use std::time::Duration;
+
+use design_patterns::creational::singleton::Global;
+
+#[derive(Debug)]
+struct Config {
+    generation: usize,
+}
+
+static CONSTRUCTIONS: AtomicUsize = AtomicUsize::new(0);
+
+fn slow_config() -> Result<Config, String> {
+    let generation = CONSTRUCTIONS.fetch_add(1, Ordering::SeqCst) + 1;
+    // Give the other threads time to pile up behind the initializer
+    thread::sleep(Duration::from_millis(20));
+    Ok(Config { generation })
+}
+
+static CONFIG: Global<Config, String> = Global::new(slow_config);
+
+#[test]
+fn concurrent_callers_share_one_construction() {
+    const THREADS: usize = 32;
+    let barrier = Arc::new(Barrier::new(THREADS));
+    let handles: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let barrier = Arc::clone(&barrier);
+            thread::spawn(move || {
+                barrier.wait();
+                CONFIG.get().unwrap()
+            })
+        })
+        .collect();
+    let configs: Vec<Arc<Config>> = handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect();
+
+    assert_eq!(CONSTRUCTIONS.load(Ordering::SeqCst), 1);
+    assert!(configs
+        .iter()
+        .all(|config| Arc::ptr_eq(config, &configs[0])));
+    assert_eq!(configs[0].generation, 1);
+}
+
+#[test]
+fn failed_initialization_is_retried() {
+    static ATTEMPTS: AtomicUsize = AtomicUsize::new(0);
+    static FLAKY: Global<usize, String> =
+        Global::new(|| match ATTEMPTS.fetch_add(1, Ordering::SeqCst) + 1 {
+            attempt @ (1 | 2) => Err(format!("attempt {attempt} failed")),
+            attempt => Ok(attempt),
+        });
+
+    assert_eq!(FLAKY.get().unwrap_err(), "attempt 1 failed");
+    assert!(!FLAKY.is_initialized());
+    assert_eq!(FLAKY.get().unwrap_err(), "attempt 2 failed");
+    assert_eq!(*FLAKY.get().unwrap(), 3);
+    // Once it succeeded it is never run again
+    assert_eq!(*FLAKY.get().unwrap(), 3);
+    assert_eq!(ATTEMPTS.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn panicking_initialization_is_retried() {
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+    static FRAGILE: Global<&str> = Global::new(|| {
+        if CALLS.fetch_add(1, Ordering::SeqCst) == 0 {
+            panic!("first call panics");
+        }
+        Ok("ready")
+    });
+
+    assert!(thread::spawn(|| FRAGILE.instance()).join().is_err());
+    assert_eq!(*FRAGILE.instance(), "ready");
+}
+
+#[test]
+fn reset_starts_over_without_invalidating_old_handles() {
+    static BUILT: AtomicUsize = AtomicUsize::new(0);
+    static COUNTER: Global<usize> = Global::new(|| Ok(BUILT.fetch_add(1, Ordering::SeqCst)));
+
+    let first = COUNTER.instance();
+    assert!(COUNTER.try_get().is_some());
+
+    let dropped = COUNTER.reset().unwrap();
+    assert!(Arc::ptr_eq(&first, &dropped));
+    assert!(COUNTER.try_get().is_none());
+    assert!(COUNTER.reset().is_none());
+
+    let second = COUNTER.instance();
+    assert_eq!((*first, *second), (0, 1));
+}
@@ -0,0 +1,313 @@
+use design_patterns::creational::builder::upgrade::{
+    PlanError, ProvisionError, Provisioner, SimulatedProvisioner, Step, UpgradePlan,
+};
+use design_patterns::creational::builder::{
+    KubernetesCluster, KubernetesClusterBuilder, KubernetesVersion, NodePoolBuilder, Violation,
+};
+
+fn cluster(version: &str, auto_upgrade: bool, pools: &[(&str, u32)]) -> KubernetesCluster {
+    let mut builder = KubernetesClusterBuilder::new("prod".to_string(), version.to_string());
+    builder.auto_upgrade(auto_upgrade);
+    for (name, max_nodes) in pools {
+        builder.node_pool(
+            NodePoolBuilder::new(name.to_string(), "e2-standard-4".to_string())
+                .autoscaling(1, *max_nodes),
+        );
+    }
+    builder.build().unwrap()
+}
+
+fn describe(plan: &UpgradePlan) -> Vec<String> {
+    plan.steps().iter().map(Step::to_string).collect()
+}
+
+#[test]
+fn plan_is_ordered_and_marks_disruptive_steps() {
+    let current = cluster(
+        "1.29",
+        true,
+        &[("system", 3), ("compute", 10), ("spot", 50)],
+    );
+    let desired = cluster(
+        "1.30.1",
+        false,
+        &[("system", 3), ("compute", 5), ("gpu", 4)],
+    );
+
+    let plan = UpgradePlan::diff(&current, &desired).unwrap();
+
+    assert_eq!(
+        describe(&plan),
+        [
+            "turn auto-upgrade off",
+            "upgrade control plane from 1.29 to 1.30.1",
+            "add node pool gpu (e2-standard-4 x 1..4)",
+            "upgrade node pool system from 1.29 to 1.30.1 (disruptive)",
+            "upgrade node pool compute from 1.29 to 1.30.1 (disruptive)",
+            "resize node pool compute to 1..5 (disruptive)",
+            "remove node pool spot (disruptive)",
+        ]
+    );
+    assert!(plan.is_disruptive());
+}
+
+#[test]
+fn identical_specs_need_no_steps() {
+    let current = cluster("1.29", true, &[("system", 3)]);
+    let plan = UpgradePlan::diff(&current, &current.clone()).unwrap();
+
+    assert!(plan.is_empty());
+    assert_eq!(plan.to_string(), "nothing to do\n");
+}
+
+#[test]
+fn non_disruptive_changes() {
+    let current = cluster("1.29", false, &[("system", 3)]);
+    let desired = cluster("1.29", true, &[("system", 6), ("batch", 2)]);
+
+    let plan = UpgradePlan::diff(&current, &desired).unwrap();
+
+    assert!(!plan.is_disruptive());
+    assert_eq!(plan.steps().last(), Some(&Step::SetAutoUpgrade(true)));
+}
+
+#[test]
+fn auto_upgrade_is_off_while_the_version_changes() {
+    let current = cluster("1.29", true, &[("system", 3)]);
+    let desired = cluster("1.30", true, &[("system", 3)]);
+
+    let plan = UpgradePlan::diff(&current, &desired).unwrap();
+
+    assert_eq!(
+        describe(&plan),
+        [
+            "turn auto-upgrade off",
+            "upgrade control plane from 1.29 to 1.30",
+            "upgrade node pool system from 1.29 to 1.30 (disruptive)",
+            "turn auto-upgrade on",
+        ]
+    );
+
+    // Without a version change there is nothing to race
+    let desired = cluster("1.29", true, &[("system", 6)]);
+    let plan = UpgradePlan::diff(&current, &desired).unwrap();
+    assert_eq!(describe(&plan), ["resize node pool system to 1..6"]);
+}
+
+#[test]
+fn replaced_pools_are_not_upgraded_first() {
+    let current = cluster("1.29", false, &[("system", 3), ("compute", 10)]);
+    let mut desired = KubernetesClusterBuilder::new("prod".to_string(), "1.30".to_string());
+    desired
+        .node_pool(
+            NodePoolBuilder::new("system".to_string(), "e2-standard-4".to_string())
+                .autoscaling(1, 3),
+        )
+        .node_pool(
+            NodePoolBuilder::new("compute".to_string(), "n2-standard-8".to_string())
+                .autoscaling(1, 10),
+        );
+    let desired = desired.build().unwrap();
+
+    let plan = UpgradePlan::diff(&current, &desired).unwrap();
+
+    assert_eq!(
+        describe(&plan),
+        [
+            "upgrade control plane from 1.29 to 1.30",
+            "upgrade node pool system from 1.29 to 1.30 (disruptive)",
+            "replace node pool compute (disruptive)",
+        ]
+    );
+    let compute_steps = plan.steps().iter().filter(|step| match step {
+        Step::UpgradeNodePool { pool, .. } => pool == "compute",
+        Step::ReplaceNodePool { to, .. } => to.name() == "compute",
+        _ => false,
+    });
+    assert_eq!(compute_steps.filter(|step| step.is_disruptive()).count(), 1);
+
+    let mut provisioner = SimulatedProvisioner::new(&current);
+    plan.apply(&mut provisioner).unwrap();
+    assert_eq!(provisioner.cluster(), desired);
+    assert_eq!(
+        provisioner.pool_version("compute"),
+        Some(KubernetesVersion::channel(1, 30))
+    );
+}
+
+#[test]
+fn unsupported_version_changes_are_rejected() {
+    let plan = |from: &str, to: &str| {
+        UpgradePlan::diff(&cluster(from, false, &[]), &cluster(to, false, &[]))
+    };
+
+    assert_eq!(
+        plan("1.28.3", "1.30"),
+        Err(PlanError::SkippedMinorVersions {
+            from: KubernetesVersion::release(1, 28, 3),
+            to: KubernetesVersion::channel(1, 30),
+        })
+    );
+    assert_eq!(
+        plan("1.28.3", "1.30").unwrap_err().to_string(),
+        "can't upgrade from 1.28.3 to 1.30, upgrade to 1.29 first"
+    );
+    assert!(matches!(
+        plan("1.30", "1.29"),
+        Err(PlanError::Downgrade { .. })
+    ));
+    assert!(matches!(
+        plan("1.29.4", "1.29.2"),
+        Err(PlanError::Downgrade { .. })
+    ));
+    assert!(plan("1.29.2", "1.29.4").is_ok());
+
+    // Moving to the channel of the same minor version upgrades nothing
+    let current = cluster("1.29.4", false, &[("system", 3)]);
+    let desired = cluster("1.29", true, &[("system", 3)]);
+    let tracking = UpgradePlan::diff(&current, &desired).unwrap();
+    assert_eq!(
+        describe(&tracking),
+        [
+            "follow the 1.29 channel instead of 1.29.4",
+            "turn auto-upgrade on",
+        ]
+    );
+    assert!(!tracking.is_disruptive());
+    let mut provisioner = SimulatedProvisioner::new(&current);
+    tracking.apply(&mut provisioner).unwrap();
+    assert_eq!(provisioner.cluster(), desired);
+    assert_eq!(
+        provisioner.pool_version("system"),
+        Some(KubernetesVersion::channel(1, 29))
+    );
+
+    let other = KubernetesClusterBuilder::new("staging".to_string(), "1.29".to_string())
+        .build()
+        .unwrap();
+    assert!(matches!(
+        UpgradePlan::diff(&cluster("1.29", false, &[]), &other),
+        Err(PlanError::ClusterMismatch { .. })
+    ));
+}
+
+#[test]
+fn simulated_rollout_reaches_the_desired_spec() {
+    let current = cluster("1.29", true, &[("system", 3), ("spot", 50)]);
+    let desired = cluster("1.30", true, &[("system", 5), ("gpu", 4)]);
+    let plan = UpgradePlan::diff(&current, &desired).unwrap();
+
+    let mut provisioner = SimulatedProvisioner::new(&current);
+    plan.apply(&mut provisioner).unwrap();
+
+    let cluster = provisioner.cluster();
+    assert_eq!(cluster.version(), "1.30");
+    assert_eq!(cluster.node_pools().len(), 2);
+    for pool in desired.node_pools() {
+        assert_eq!(cluster.node_pool(pool.name()), Some(pool));
+        assert_eq!(
+            provisioner.pool_version(pool.name()),
+            Some(KubernetesVersion::channel(1, 30))
+        );
+    }
+    assert_eq!(provisioner.log().len(), plan.steps().len());
+    // Nothing left to do
+    assert!(UpgradePlan::diff(&cluster, &desired).unwrap().is_empty());
+}
+
+#[test]
+fn failed_step_stops_the_rollout() {
+    let current = cluster("1.29", false, &[("system", 3), ("compute", 10)]);
+    let desired = cluster("1.30", false, &[("system", 3), ("compute", 10)]);
+    let plan = UpgradePlan::diff(&current, &desired).unwrap();
+
+    let mut provisioner = SimulatedProvisioner::new(&current);
+    provisioner
+        .fail_when(|step| matches!(step, Step::UpgradeNodePool { pool, .. } if pool == "compute"));
+    let err = plan.apply(&mut provisioner).unwrap_err();
+
+    assert_eq!(err.applied, 2);
+    assert!(matches!(err.error, ProvisionError::Simulated(_)));
+    // Half way: control plane and system are upgraded, compute isn't
+    assert_eq!(
+        provisioner.control_plane_version(),
+        KubernetesVersion::channel(1, 30)
+    );
+    assert_eq!(
+        provisioner.pool_version("system"),
+        Some(KubernetesVersion::channel(1, 30))
+    );
+    assert_eq!(
+        provisioner.pool_version("compute"),
+        Some(KubernetesVersion::channel(1, 29))
+    );
+
+    // Once the cause is fixed the rollout continues where it stopped
+    provisioner.stop_failing();
+    plan.resume(&mut provisioner, err.applied).unwrap();
+    assert_eq!(
+        provisioner.pool_version("compute"),
+        Some(KubernetesVersion::channel(1, 30))
+    );
+    assert_eq!(provisioner.log().len(), plan.steps().len());
+}
+
+#[test]
+fn provisioner_rejects_steps_out_of_order() {
+    let current = cluster("1.29", false, &[("system", 3)]);
+    let mut provisioner = SimulatedProvisioner::new(&current);
+
+    let upgrade_pool = Step::UpgradeNodePool {
+        pool: "system".to_string(),
+        from: KubernetesVersion::channel(1, 29),
+        to: KubernetesVersion::channel(1, 30),
+    };
+    assert!(matches!(
+        provisioner.apply(&upgrade_pool),
+        Err(ProvisionError::VersionSkew { .. })
+    ));
+    assert_eq!(
+        provisioner.apply(&Step::RemoveNodePool("gpu".to_string())),
+        Err(ProvisionError::UnknownPool("gpu".to_string()))
+    );
+    assert!(provisioner.log().is_empty());
+}
+
+#[test]
+fn provisioner_rejects_steps_that_leave_an_invalid_spec() {
+    let current = cluster("1.29.4", false, &[("system", 3)]);
+    let mut provisioner = SimulatedProvisioner::new(&current);
+    let mut violations = |step: Step| match provisioner.apply(&step) {
+        Err(ProvisionError::InvalidSpec(err)) => err.violations().to_vec(),
+        other => panic!("{step} was not rejected: {other:?}"),
+    };
+
+    assert_eq!(
+        violations(Step::SetAutoUpgrade(true)),
+        [Violation::AutoUpgradeRequiresChannel(
+            KubernetesVersion::release(1, 29, 4)
+        )]
+    );
+    assert_eq!(
+        violations(Step::UpgradeControlPlane {
+            from: KubernetesVersion::release(1, 29, 4),
+            to: KubernetesVersion::channel(1, 32),
+        }),
+        [Violation::UnsupportedVersion(KubernetesVersion::channel(
+            1, 32
+        ))]
+    );
+    assert!(matches!(
+        violations(Step::ResizeNodePool {
+            pool: "system".to_string(),
+            min_nodes: 5,
+            max_nodes: 2,
+            shrinks: true,
+        })[..],
+        [Violation::InvalidAutoscaling { .. }]
+    ));
+
+    // Nothing was applied
+    assert_eq!(provisioner.cluster(), current);
+    assert!(provisioner.log().is_empty());
+}